@@ -0,0 +1,200 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+mod pedersen381 {
+    use ark_bls12_381::Bls12_381;
+    use zkmember::commitments::pedersen381;
+
+    super::bench_batch!(pedersen381, Bls12_381, 64, 2, 4, 8, 16);
+}
+
+mod pedersen761 {
+    use ark_bw6_761::BW6_761;
+    pub use zkmember::commitments::pedersen761;
+
+    super::bench_batch!(pedersen761, BW6_761, 64, 2, 4, 8, 16);
+}
+
+mod pedersen377 {
+    use ark_bls12_377::Bls12_377;
+    use zkmember::commitments::pedersen377;
+
+    super::bench_batch!(pedersen377, Bls12_377, 64, 2, 4, 8, 16);
+}
+
+fn criterion_config(measurement_secs: u64) -> Criterion {
+    Criterion::default()
+        .measurement_time(Duration::from_secs(measurement_secs))
+        .sample_size(10)
+}
+
+criterion_group! {
+    name = pedersen381_benches;
+    config = criterion_config(30);
+    targets = pedersen381::bench_batch
+}
+criterion_group! {
+    name = pedersen761_benches;
+    config = criterion_config(30);
+    targets = pedersen761::bench_batch
+}
+criterion_group! {
+    name = pedersen377_benches;
+    config = criterion_config(30);
+    targets = pedersen377::bench_batch
+}
+criterion_main!(
+    pedersen381_benches,
+    pedersen761_benches,
+    pedersen377_benches
+);
+
+/// Compares one `BatchMerkleTreeCircuit` proof over `k` members against `k`
+/// separate `MerkleTreeCircuit` proofs, for a tree of `$num_members` members.
+#[macro_export]
+macro_rules! bench_batch {
+    ($module:ident, $curve:ident, $num_members:expr, $($k:expr),+) => {
+        use $module::{
+            common::{new_membership_tree, LeafHash, TwoToOneHash},
+            constraint::{BatchMerkleTreeCircuit, MerkleTreeCircuit},
+        };
+
+        use ark_crypto_primitives::{
+            crh::{TwoToOneCRH, CRH},
+            SNARK,
+        };
+        use ark_groth16::Groth16;
+        use criterion::{black_box, Criterion};
+
+        use zkmember::member::{generate_members, Identity};
+
+        pub fn bench_batch(c: &mut Criterion) {
+            let mut rng = ark_std::test_rng();
+            let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+            let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+            let mut members = Box::new(vec![]);
+            generate_members(&mut members, $num_members);
+
+            // Give every mock member an identity, so it can join a batch
+            let identities = (0..members.len())
+                .map(|_| Identity::random())
+                .collect::<Vec<_>>();
+            let members = members
+                .iter()
+                .zip(&identities)
+                .map(|(member, identity)| {
+                    member.clone().with_identity_commitment(
+                        identity.commitment::<LeafHash>(&leaf_crh_params).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            // Hash mock members
+            let mut leaves = members
+                .iter()
+                .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+                .collect::<Vec<_>>();
+
+            // Construct membership Merkle tree
+            let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+            let root = tree.root();
+
+            // A single-leaf circuit is the same for every k
+            let single_circuit = |index: usize| MerkleTreeCircuit {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root,
+                leaf_hash: leaves[index],
+                authentication_path: Some(tree.generate_proof(index).unwrap()),
+            };
+            let (single_pk, single_vk) =
+                Groth16::<$curve>::circuit_specific_setup(single_circuit(0), &mut rng).unwrap();
+
+            for &k in &[$($k),+] {
+                let batch_circuit = BatchMerkleTreeCircuit {
+                    leaf_crh_params: leaf_crh_params.clone(),
+                    two_to_one_crh_params: two_to_one_crh_params.clone(),
+                    root,
+                    members: (0..k).map(|index| Some(members[index].clone())).collect(),
+                    identities: (0..k)
+                        .map(|index| Some(identities[index].clone()))
+                        .collect(),
+                    authentication_paths: (0..k)
+                        .map(|index| Some(tree.generate_proof(index).unwrap()))
+                        .collect(),
+                };
+                let (batch_pk, batch_vk) =
+                    Groth16::<$curve>::circuit_specific_setup(batch_circuit.clone(), &mut rng)
+                        .unwrap();
+
+                c.bench_function(
+                    format!("{}_batch_prove_{}", stringify!($curve), k).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            let proof =
+                                Groth16::<$curve>::prove(&batch_pk, batch_circuit.clone(), &mut rng)
+                                    .unwrap();
+                            black_box(proof);
+                        });
+                    },
+                );
+
+                c.bench_function(
+                    format!("{}_separate_prove_{}", stringify!($curve), k).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            for index in 0..k {
+                                let proof = Groth16::<$curve>::prove(
+                                    &single_pk,
+                                    single_circuit(index),
+                                    &mut rng,
+                                )
+                                .unwrap();
+                                black_box(proof);
+                            }
+                        });
+                    },
+                );
+
+                let batch_proof =
+                    Groth16::<$curve>::prove(&batch_pk, batch_circuit, &mut rng).unwrap();
+                let separate_proofs = (0..k)
+                    .map(|index| {
+                        Groth16::<$curve>::prove(&single_pk, single_circuit(index), &mut rng)
+                            .unwrap()
+                    })
+                    .collect::<Vec<_>>();
+
+                c.bench_function(
+                    format!("{}_batch_verify_{}", stringify!($curve), k).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            let is_valid =
+                                Groth16::<$curve>::verify(&batch_vk, &[root], &batch_proof)
+                                    .unwrap();
+                            assert!(is_valid);
+                        });
+                    },
+                );
+
+                c.bench_function(
+                    format!("{}_separate_verify_{}", stringify!($curve), k).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            for (index, proof) in separate_proofs.iter().enumerate() {
+                                let is_valid = Groth16::<$curve>::verify(
+                                    &single_vk,
+                                    &[root, leaves[index]],
+                                    proof,
+                                )
+                                .unwrap();
+                                assert!(is_valid);
+                            }
+                        });
+                    },
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,182 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+mod pedersen381 {
+    use ark_bls12_381::Bls12_381;
+    use zkmember::commitments::pedersen381;
+
+    super::bench_gm17!(
+        pedersen381,
+        Bls12_381,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod pedersen761 {
+    use ark_bw6_761::BW6_761;
+    use zkmember::commitments::pedersen761;
+
+    super::bench_gm17!(pedersen761, BW6_761, 16, 50, 64, 100, 128, 512, 1000, 1024);
+}
+
+mod pedersen377 {
+    use ark_bls12_377::Bls12_377;
+    use zkmember::commitments::pedersen377;
+
+    super::bench_gm17!(
+        pedersen377,
+        Bls12_377,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod pedersen254 {
+    use ark_bn254::Bn254;
+    use zkmember::commitments::pedersen254;
+
+    super::bench_gm17!(pedersen254, Bn254, 16, 50, 64, 100, 128, 512, 1000, 1024);
+}
+
+fn criterion_config(measurement_secs: u64) -> Criterion {
+    Criterion::default()
+        .measurement_time(Duration::from_secs(measurement_secs))
+        .sample_size(10)
+}
+
+criterion_group! {
+    name = pedersen381_benches;
+    config = criterion_config(30);
+    targets = pedersen381::bench_gm17
+}
+criterion_group! {
+    name = pedersen761_benches;
+    config = criterion_config(30);
+    targets = pedersen761::bench_gm17
+}
+criterion_group! {
+    name = pedersen377_benches;
+    config = criterion_config(30);
+    targets = pedersen377::bench_gm17
+}
+criterion_group! {
+    name = pedersen254_benches;
+    config = criterion_config(30);
+    targets = pedersen254::bench_gm17
+}
+criterion_main!(
+    pedersen381_benches,
+    pedersen761_benches,
+    pedersen377_benches,
+    pedersen254_benches
+);
+
+#[macro_export]
+macro_rules! bench_gm17 {
+    ($module:ident, $curve:ident, $($num_members:expr),+) => {
+        use $module::{
+            common::{new_membership_tree, LeafHash, TwoToOneHash},
+            constraint::MerkleTreeCircuit,
+        };
+
+        use ark_crypto_primitives::{
+            crh::{TwoToOneCRH, CRH},
+            SNARK,
+        };
+        use ark_ec::PairingEngine;
+        use ark_gm17::GM17;
+        use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+        use criterion::{black_box, Criterion};
+        use rand::Rng;
+
+        use zkmember::member::{generate_members, Member};
+
+        pub fn bench_gm17(c: &mut Criterion) {
+			let mut rng = ark_std::test_rng();
+			let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+			let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+			// Max constraints
+			let mut members = Box::new(vec![]);
+			let max_members = [$($num_members),+].iter().max().unwrap();
+			generate_members(&mut members, *max_members);
+
+            for &num_members in &[$($num_members),+] {
+				let members = members[0..num_members].to_vec();
+
+				println!("{} members", members.len());
+
+                // Hash mock members
+                let mut leaves = members
+                    .iter()
+                    .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+                    .collect::<Vec<_>>();
+
+                // Construct membership Merkle tree
+                let tree =
+                    new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+                let root = tree.root();
+
+                // Fetch random member from the tree
+                let index = black_box(rand::rng().random_range(0..num_members as u32));
+                let path = black_box(tree.generate_proof(index as usize).unwrap());
+                let member: &Member = members.get(index as usize).unwrap();
+
+                // Initialize circuit constraints struct for merkle tree
+                let circuit = MerkleTreeCircuit {
+                    leaf_crh_params: leaf_crh_params.clone(),
+                    two_to_one_crh_params: two_to_one_crh_params.clone(),
+                    root,
+                    leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
+                    authentication_path: Some(path),
+                };
+
+                // Report circuit size so hash backends can be compared
+                let cs = ConstraintSystem::<<$curve as PairingEngine>::Fr>::new_ref();
+                circuit.clone().generate_constraints(cs.clone()).unwrap();
+                println!("{} constraints", cs.num_constraints());
+
+                let (pk, vk) =
+                    GM17::<$curve>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+
+                c.bench_function(
+                    format!("{}_{}_gm17_prove_{}", stringify!($module), stringify!($curve), num_members).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            let proof =
+                                GM17::<$curve>::prove(&pk, circuit.clone(), &mut rng).unwrap();
+                            black_box(proof);
+                        });
+                    },
+                );
+
+                let proof = GM17::<$curve>::prove(&pk, circuit, &mut rng).unwrap();
+                let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
+
+                c.bench_function(
+                    format!("{}_{}_gm17_verify_{}", stringify!($module), stringify!($curve), num_members).as_str(),
+                    |b| {
+                        b.iter(|| {
+                            let is_valid =
+                                GM17::<$curve>::verify(&vk, &public_input, &proof).unwrap();
+                            assert!(is_valid);
+                        });
+                    },
+                );
+            }
+        }
+    };
+}
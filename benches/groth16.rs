@@ -28,6 +28,85 @@ mod pedersen761 {
     super::bench_groth16!(pedersen761, BW6_761, 16, 50, 64, 100, 128, 512, 1000, 1024);
 }
 
+mod pedersen377 {
+    use ark_bls12_377::Bls12_377;
+    use zkmember::commitments::pedersen377;
+
+    super::bench_groth16!(
+        pedersen377,
+        Bls12_377,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod pedersen254 {
+    use ark_bn254::Bn254;
+    use zkmember::commitments::pedersen254;
+
+    super::bench_groth16!(pedersen254, Bn254, 16, 50, 64, 100, 128, 512, 1000, 1024);
+}
+
+mod poseidon381 {
+    use ark_bls12_381::Bls12_381;
+    use zkmember::commitments::poseidon381;
+
+    super::bench_groth16!(
+        poseidon381,
+        Bls12_381,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood381 {
+    use ark_bls12_381::Bls12_381;
+    use zkmember::commitments::bowe_hopwood381;
+
+    super::bench_groth16!(
+        bowe_hopwood381,
+        Bls12_381,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood761 {
+    use ark_bw6_761::BW6_761;
+    use zkmember::commitments::bowe_hopwood761;
+
+    super::bench_groth16!(
+        bowe_hopwood761,
+        BW6_761,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
 fn criterion_config(measurement_secs: u64) -> Criterion {
     Criterion::default()
         .measurement_time(Duration::from_secs(measurement_secs))
@@ -44,7 +123,40 @@ criterion_group! {
     config = criterion_config(30);
     targets = pedersen761::bench_groth16
 }
-criterion_main!(pedersen381_benches, pedersen761_benches);
+criterion_group! {
+    name = pedersen377_benches;
+    config = criterion_config(30);
+    targets = pedersen377::bench_groth16
+}
+criterion_group! {
+    name = pedersen254_benches;
+    config = criterion_config(30);
+    targets = pedersen254::bench_groth16
+}
+criterion_group! {
+    name = poseidon381_benches;
+    config = criterion_config(30);
+    targets = poseidon381::bench_groth16
+}
+criterion_group! {
+    name = bowe_hopwood381_benches;
+    config = criterion_config(30);
+    targets = bowe_hopwood381::bench_groth16
+}
+criterion_group! {
+    name = bowe_hopwood761_benches;
+    config = criterion_config(30);
+    targets = bowe_hopwood761::bench_groth16
+}
+criterion_main!(
+    pedersen381_benches,
+    pedersen761_benches,
+    pedersen377_benches,
+    pedersen254_benches,
+    poseidon381_benches,
+    bowe_hopwood381_benches,
+    bowe_hopwood761_benches
+);
 
 #[macro_export]
 macro_rules! bench_groth16 {
@@ -58,7 +170,9 @@ macro_rules! bench_groth16 {
             crh::{TwoToOneCRH, CRH},
             SNARK,
         };
+        use ark_ec::PairingEngine;
         use ark_groth16::Groth16;
+        use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
         use criterion::{black_box, Criterion};
         use rand::Rng;
 
@@ -82,7 +196,7 @@ macro_rules! bench_groth16 {
                 // Hash mock members
                 let mut leaves = members
                     .iter()
-                    .map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+                    .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
                     .collect::<Vec<_>>();
 
                 // Construct membership Merkle tree
@@ -100,15 +214,20 @@ macro_rules! bench_groth16 {
                     leaf_crh_params: leaf_crh_params.clone(),
                     two_to_one_crh_params: two_to_one_crh_params.clone(),
                     root,
-                    leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+                    leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
                     authentication_path: Some(path),
                 };
 
+                // Report circuit size so hash backends can be compared
+                let cs = ConstraintSystem::<<$curve as PairingEngine>::Fr>::new_ref();
+                circuit.clone().generate_constraints(cs.clone()).unwrap();
+                println!("{} constraints", cs.num_constraints());
+
                 let (pk, vk) =
                     Groth16::<$curve>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
 
                 c.bench_function(
-                    format!("{}_groth16_prove_{}", stringify!($curve), num_members).as_str(),
+                    format!("{}_{}_groth16_prove_{}", stringify!($module), stringify!($curve), num_members).as_str(),
                     |b| {
                         b.iter(|| {
                             let proof =
@@ -119,10 +238,10 @@ macro_rules! bench_groth16 {
                 );
 
                 let proof = Groth16::<$curve>::prove(&pk, circuit, &mut rng).unwrap();
-                let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+                let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
                 c.bench_function(
-                    format!("{}_groth16_verify_{}", stringify!($curve), num_members).as_str(),
+                    format!("{}_{}_groth16_verify_{}", stringify!($module), stringify!($curve), num_members).as_str(),
                     |b| {
                         b.iter(|| {
                             let is_valid =
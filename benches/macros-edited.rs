@@ -30,7 +30,7 @@ macro_rules! bench_groth16 {
 				// Hash mock members
 				let mut leaves = members
 					.iter()
-					.map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+					.map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
 					.collect::<Vec<_>>();
 
 				// Construct membership Merkle tree
@@ -47,7 +47,7 @@ macro_rules! bench_groth16 {
 					leaf_crh_params: leaf_crh_params.clone(),
 					two_to_one_crh_params: two_to_one_crh_params,
 					root,
-					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
 					authentication_path: Some(path),
 				};
 
@@ -75,7 +75,7 @@ macro_rules! bench_groth16 {
 				);
 
 				let proof = Groth16::<$curve>::prove(&pk, circuit, &mut rng).unwrap();
-				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
 				c.bench_function(
 					format!("{}_groth16_verify", stringify!($curve)).as_str(),
@@ -133,7 +133,7 @@ macro_rules! bench_marlin {
 				// Hash mock members
 				let mut leaves = members
 					.iter()
-					.map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+					.map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
 					.collect::<Vec<_>>();
 
 				// Construct membership Merkle tree
@@ -150,7 +150,7 @@ macro_rules! bench_marlin {
 					leaf_crh_params: leaf_crh_params.clone(),
 					two_to_one_crh_params: two_to_one_crh_params.clone(),
 					root,
-					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
 					authentication_path: Some(path),
 				};
 
@@ -206,7 +206,7 @@ macro_rules! bench_marlin {
 				// );
 
 				// let proof = MarlinM::prove(&pk.clone(), circuit.clone(), &mut rng).unwrap();
-				// let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+				// let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
 				// c.bench_function(
 				// 	format!("{}_marlin_verify", stringify!($curve)).as_str(),
@@ -232,7 +232,7 @@ macro_rules! bench_marlin {
 
 					// Initialize proof inputs
 					let root = tree.root();
-					let leaf_hash = members.get(index as usize).unwrap().hash::<LeafHash>(&leaf_crh_params);
+					let leaf_hash = members.get(index as usize).unwrap().hash::<LeafHash>(&leaf_crh_params).unwrap();
 					let path = tree.generate_proof(index as usize).unwrap();
 
 					// Initialize circuit constraints struct for merkle tree
@@ -11,6 +11,7 @@ mod marlin381 {
         pedersen381,
         Bls12_381,
         Fr,
+        KzgPC,
         16,
         50,
         64,
@@ -26,6 +27,7 @@ mod marlin381 {
         pedersen381,
         Bls12_381,
         Fr,
+        KzgPC,
         16,
         50,
         64,
@@ -46,6 +48,7 @@ mod marlin761 {
         pedersen761,
         BW6_761,
         Fr,
+        KzgPC,
         16,
         50,
         64,
@@ -61,6 +64,451 @@ mod marlin761 {
         pedersen761,
         BW6_761,
         Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin377 {
+    use ark_bls12_377::{Bls12_377, Fr};
+    use zkmember::commitments::pedersen377;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen377,
+        Bls12_377,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen377,
+        Bls12_377,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin254 {
+    use ark_bn254::{Bn254, Fr};
+    use zkmember::commitments::pedersen254;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen254,
+        Bn254,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen254,
+        Bn254,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod poseidon381 {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use zkmember::commitments::poseidon381;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        poseidon381,
+        Bls12_381,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        poseidon381,
+        Bls12_381,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood381 {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use zkmember::commitments::bowe_hopwood381;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        bowe_hopwood381,
+        Bls12_381,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        bowe_hopwood381,
+        Bls12_381,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood761 {
+    use ark_bw6_761::{Fr, BW6_761};
+    use zkmember::commitments::bowe_hopwood761;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        bowe_hopwood761,
+        BW6_761,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        bowe_hopwood761,
+        BW6_761,
+        Fr,
+        KzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin381_sonic {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use zkmember::commitments::pedersen381;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin761_sonic {
+    use ark_bw6_761::{Fr, BW6_761};
+    use zkmember::commitments::pedersen761;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen761,
+        BW6_761,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen761,
+        BW6_761,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin377_sonic {
+    use ark_bls12_377::{Bls12_377, Fr};
+    use zkmember::commitments::pedersen377;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen377,
+        Bls12_377,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen377,
+        Bls12_377,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod marlin254_sonic {
+    use ark_bn254::{Bn254, Fr};
+    use zkmember::commitments::pedersen254;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        pedersen254,
+        Bn254,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        pedersen254,
+        Bn254,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod poseidon381_sonic {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use zkmember::commitments::poseidon381;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        poseidon381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        poseidon381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood381_sonic {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use zkmember::commitments::bowe_hopwood381;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        bowe_hopwood381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        bowe_hopwood381,
+        Bls12_381,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+}
+
+mod bowe_hopwood761_sonic {
+    use ark_bw6_761::{Fr, BW6_761};
+    use zkmember::commitments::bowe_hopwood761;
+
+    #[cfg(not(feature = "universal-constraints"))]
+    super::bench_marlin!(
+        bowe_hopwood761,
+        BW6_761,
+        Fr,
+        SonicKzgPC,
+        16,
+        50,
+        64,
+        100,
+        128,
+        512,
+        1000,
+        1024
+    );
+
+    #[cfg(feature = "universal-constraints")]
+    super::bench_marlin_universal!(
+        bowe_hopwood761,
+        BW6_761,
+        Fr,
+        SonicKzgPC,
         16,
         50,
         64,
@@ -88,18 +536,92 @@ criterion_group! {
     config = criterion_config(60);
     targets = marlin761::bench_marlin
 }
+criterion_group! {
+    name = marlin377_benches;
+    config = criterion_config(60);
+    targets = marlin377::bench_marlin
+}
+criterion_group! {
+    name = marlin254_benches;
+    config = criterion_config(60);
+    targets = marlin254::bench_marlin
+}
+
+criterion_group! {
+    name = poseidon381_benches;
+    config = criterion_config(60);
+    targets = poseidon381::bench_marlin
+}
+
+criterion_group! {
+    name = bowe_hopwood381_benches;
+    config = criterion_config(60);
+    targets = bowe_hopwood381::bench_marlin
+}
+criterion_group! {
+    name = bowe_hopwood761_benches;
+    config = criterion_config(60);
+    targets = bowe_hopwood761::bench_marlin
+}
+
+criterion_group! {
+    name = marlin381_sonic_benches;
+    config = criterion_config(60);
+    targets = marlin381_sonic::bench_marlin
+}
+criterion_group! {
+    name = marlin761_sonic_benches;
+    config = criterion_config(60);
+    targets = marlin761_sonic::bench_marlin
+}
+criterion_group! {
+    name = marlin377_sonic_benches;
+    config = criterion_config(60);
+    targets = marlin377_sonic::bench_marlin
+}
+criterion_group! {
+    name = marlin254_sonic_benches;
+    config = criterion_config(60);
+    targets = marlin254_sonic::bench_marlin
+}
+criterion_group! {
+    name = poseidon381_sonic_benches;
+    config = criterion_config(60);
+    targets = poseidon381_sonic::bench_marlin
+}
+criterion_group! {
+    name = bowe_hopwood381_sonic_benches;
+    config = criterion_config(60);
+    targets = bowe_hopwood381_sonic::bench_marlin
+}
+criterion_group! {
+    name = bowe_hopwood761_sonic_benches;
+    config = criterion_config(60);
+    targets = bowe_hopwood761_sonic::bench_marlin
+}
 
-criterion_main!(marlin381_benches, marlin761_benches);
+criterion_main!(
+    marlin381_benches,
+    marlin761_benches,
+    marlin377_benches,
+    marlin254_benches,
+    poseidon381_benches,
+    bowe_hopwood381_benches,
+    bowe_hopwood761_benches,
+    marlin381_sonic_benches,
+    marlin761_sonic_benches,
+    marlin377_sonic_benches,
+    marlin254_sonic_benches,
+    poseidon381_sonic_benches,
+    bowe_hopwood381_sonic_benches,
+    bowe_hopwood761_sonic_benches
+);
 
 #[macro_export]
 macro_rules! bench_marlin_universal {
-    ($module:ident, $curve:ident, $field:ident, $($num_members:expr),+) => {
+    ($module:ident, $curve:ident, $field:ident, $pc:ident, $($num_members:expr),+) => {
         use ark_crypto_primitives::crh::{TwoToOneCRH, CRH};
-        use ark_marlin::Marlin;
-        use ark_poly::univariate::DensePolynomial;
-        use ark_poly_commit::marlin_pc::MarlinKZG10;
         use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
-        use blake2::Blake2s;
         use criterion::{black_box, Criterion};
         use rand::Rng;
         use $module::{
@@ -107,11 +629,9 @@ macro_rules! bench_marlin_universal {
             constraint::MerkleTreeCircuit,
         };
 
+        use zkmember::marlin::{prove, verify, $pc, MembershipSrs};
         use zkmember::member::{generate_members, Member};
 
-        type PC = MarlinKZG10<$curve, DensePolynomial<$field>>;
-        type MarlinM = Marlin<$field, PC, Blake2s>;
-
         pub fn bench_marlin(c: &mut Criterion) {
 			let mut rng = ark_std::test_rng();
 			let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
@@ -126,7 +646,7 @@ macro_rules! bench_marlin_universal {
 
 			// Compute global constraints (gc)
 			let mut gc_leaves = members.iter().map(|member| {
-				member.hash::<LeafHash>(&leaf_crh_params)
+				member.hash::<LeafHash>(&leaf_crh_params).unwrap()
 			}).collect::<Vec<_>>();
 
 			let gc_tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut gc_leaves);
@@ -140,41 +660,20 @@ macro_rules! bench_marlin_universal {
 				leaf_crh_params: leaf_crh_params.clone(),
 				two_to_one_crh_params: two_to_one_crh_params.clone(),
 				root: gc_root,
-				leaf_hash: gc_member.hash::<LeafHash>(&leaf_crh_params),
+				leaf_hash: gc_member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
 				authentication_path: Some(gc_path),
 			};
 
-			let cs = ConstraintSystem::<Fr>::new_ref();
-			circuit.clone().generate_constraints(cs.clone()).unwrap();
-
-			// NOTE: Correctness requires every later circuit to satisfy:
-			//  - rows ≤ num_constraints
-			let n_constraints = cs.num_constraints();
-			//  - vars ≤ num_variables
-			let n_variables = cs.num_instance_variables() + cs.num_witness_variables();
-			//  - non-zeros ≤ num_non_zero
-			let n_non_zero = 5 * n_constraints;
-
-			let srs = MarlinM::universal_setup(
-				n_constraints.next_power_of_two(),
-				n_variables.next_power_of_two(),
-				n_non_zero.next_power_of_two(),
-				&mut rng,
-			)
-			.unwrap();
-
-			let (pk, vk) = MarlinM::index(&srs, circuit.clone()).unwrap();
+			// One SRS sized for the largest tree indexes the circuit of every smaller one
+			let srs = MembershipSrs::<$field, $pc<$curve>>::for_circuit(circuit, &mut rng).unwrap();
 
 			for &num_members in &[$($num_members),+] {
-				let mut members = Box::new(members[0..num_members].to_vec());
-
-				// Pad empty members to leverage universal setup constraints
-				generate_members(&mut members, max_members - (num_members as u32));
+				let members = members[0..num_members].to_vec();
 
 				// Hash mock members
 				let mut leaves = members
 					.iter()
-					.map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+					.map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
 					.collect::<Vec<_>>();
 
 				// Construct membership Merkle tree
@@ -191,29 +690,36 @@ macro_rules! bench_marlin_universal {
 					leaf_crh_params: leaf_crh_params.clone(),
 					two_to_one_crh_params: two_to_one_crh_params.clone(),
 					root,
-					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
 					authentication_path: Some(path),
 				};
 
+				// Report circuit size so hash backends can be compared
+				let cs = ConstraintSystem::<$field>::new_ref();
+				circuit.clone().generate_constraints(cs.clone()).unwrap();
+				println!("{} constraints", cs.num_constraints());
+
+				let (pk, vk) = srs.index(circuit.clone()).unwrap();
+
 				c.bench_function(
-					format!("{}_marlin_universal_prove_{}", stringify!($curve), num_members).as_str(),
+					format!("{}_{}_{}_marlin_universal_prove_{}", stringify!($module), stringify!($curve), stringify!($pc), num_members).as_str(),
 					|b| {
 						b.iter(|| {
-							let proof = MarlinM::prove(&pk.clone(), circuit.clone(), &mut rng).unwrap();
+							let proof = prove(&pk, circuit.clone(), &mut rng).unwrap();
 							black_box(proof);
 						});
 					},
 				);
 
-				let proof = MarlinM::prove(&pk, circuit, &mut rng).unwrap();
-				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+				let proof = prove(&pk, circuit, &mut rng).unwrap();
+				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
 				c.bench_function(
-					format!("{}_marlin_universal_verify_{}", stringify!($curve), num_members).as_str(),
+					format!("{}_{}_{}_marlin_universal_verify_{}", stringify!($module), stringify!($curve), stringify!($pc), num_members).as_str(),
 					|b| {
 						b.iter(|| {
 							let is_valid =
-								MarlinM::verify(&vk.clone(), &public_input, &proof, &mut rng).unwrap();
+								verify(&vk, &public_input, &proof, &mut rng).unwrap();
 							assert!(is_valid);
 						});
 					},
@@ -225,13 +731,9 @@ macro_rules! bench_marlin_universal {
 
 #[macro_export]
 macro_rules! bench_marlin {
-    ($module:ident, $curve:ident, $field:ident, $($num_members:expr),+) => {
+    ($module:ident, $curve:ident, $field:ident, $pc:ident, $($num_members:expr),+) => {
         use ark_crypto_primitives::crh::{TwoToOneCRH, CRH};
-        use ark_marlin::Marlin;
-        use ark_poly::univariate::DensePolynomial;
-        use ark_poly_commit::marlin_pc::MarlinKZG10;
         use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
-        use blake2::Blake2s;
         use criterion::{black_box, Criterion};
         use rand::Rng;
         use $module::{
@@ -239,11 +741,9 @@ macro_rules! bench_marlin {
             constraint::MerkleTreeCircuit,
         };
 
+        use zkmember::marlin::{prove, verify, $pc, MembershipSrs};
         use zkmember::member::{generate_members, Member};
 
-        type PC = MarlinKZG10<$curve, DensePolynomial<$field>>;
-        type MarlinM = Marlin<$field, PC, Blake2s>;
-
         pub fn bench_marlin(c: &mut Criterion) {
 			let mut rng = ark_std::test_rng();
 			let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
@@ -262,7 +762,7 @@ macro_rules! bench_marlin {
 				// Hash mock members
 				let mut leaves = members
 					.iter()
-					.map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+					.map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
 					.collect::<Vec<_>>();
 
 				// Construct membership Merkle tree
@@ -279,50 +779,37 @@ macro_rules! bench_marlin {
 					leaf_crh_params: leaf_crh_params.clone(),
 					two_to_one_crh_params: two_to_one_crh_params.clone(),
 					root,
-					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+					leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
 					authentication_path: Some(path),
 				};
 
-				let cs = ConstraintSystem::<Fr>::new_ref();
+				// Report circuit size so hash backends can be compared
+				let cs = ConstraintSystem::<$field>::new_ref();
 				circuit.clone().generate_constraints(cs.clone()).unwrap();
+				println!("{} constraints", cs.num_constraints());
 
-				// NOTE: Correctness requires every later circuit to satisfy:
-				//  - rows ≤ num_constraints
-				let n_constraints = cs.num_constraints();
-				//  - vars ≤ num_variables
-				let n_variables = cs.num_instance_variables() + cs.num_witness_variables();
-				//  - non-zeros ≤ num_non_zero
-				let n_non_zero = 5 * n_constraints;
-
-				let srs = MarlinM::universal_setup(
-					n_constraints.next_power_of_two(),
-					n_variables.next_power_of_two(),
-					n_non_zero.next_power_of_two(),
-					&mut rng,
-				)
-				.unwrap();
-
-				let (pk, vk) = MarlinM::index(&srs, circuit.clone()).unwrap();
+				let srs = MembershipSrs::<$field, $pc<$curve>>::for_circuit(circuit.clone(), &mut rng).unwrap();
+				let (pk, vk) = srs.index(circuit.clone()).unwrap();
 
 				c.bench_function(
-					format!("{}_marlin_prove_{}", stringify!($curve), num_members).as_str(),
+					format!("{}_{}_{}_marlin_prove_{}", stringify!($module), stringify!($curve), stringify!($pc), num_members).as_str(),
 					|b| {
 						b.iter(|| {
-							let proof = MarlinM::prove(&pk.clone(), circuit.clone(), &mut rng).unwrap();
+							let proof = prove(&pk, circuit.clone(), &mut rng).unwrap();
 							black_box(proof);
 						});
 					},
 				);
 
-				let proof = MarlinM::prove(&pk, circuit, &mut rng).unwrap();
-				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+				let proof = prove(&pk, circuit, &mut rng).unwrap();
+				let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
 				c.bench_function(
-					format!("{}_marlin_verify_{}", stringify!($curve), num_members).as_str(),
+					format!("{}_{}_{}_marlin_verify_{}", stringify!($module), stringify!($curve), stringify!($pc), num_members).as_str(),
 					|b| {
 						b.iter(|| {
 							let is_valid =
-								MarlinM::verify(&vk.clone(), &public_input, &proof, &mut rng).unwrap();
+								verify(&vk, &public_input, &proof, &mut rng).unwrap();
 							assert!(is_valid);
 						});
 					},
@@ -0,0 +1,168 @@
+//! Groth16 phase-2 ceremony for the pedersen381 membership circuit, one step
+//! per invocation so that every participant runs in their own process:
+//!
+//! ```sh
+//! ceremony init --depth 10 --phase1 phase1.bin --out params-0.bin
+//! ceremony contribute --params params-0.bin --out params-1.bin
+//! ceremony contribute --params params-1.bin --out params-2.bin
+//! ceremony verify --depth 10 --phase1 phase1.bin --params params-2.bin
+//! ceremony finalize --depth 10 --phase1 phase1.bin --params params-2.bin --pk pk.bin --vk vk.bin
+//! ```
+//!
+//! `sample-phase1` writes a phase-1 transcript for trial runs; whoever runs it
+//! knows its secrets.
+
+use ark_bls12_381::Bls12_381;
+use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use clap::{Parser, Subcommand};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use zkmember::ceremony::{domain_size, Phase1, Phase2};
+use zkmember::commitments::pedersen::{LeafHash, MerkleTreeCircuit, TwoToOneHash};
+use zkmember::commitments::pedersen381::EdOnBls12_381;
+
+type Curve = EdOnBls12_381;
+
+#[derive(Parser)]
+#[command(about = "Multi-party Groth16 setup for the membership circuit")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Derive the initial parameters of the circuit for trees of DEPTH and
+    /// print their digest
+    Init {
+        #[arg(long)]
+        depth: usize,
+        #[arg(long)]
+        phase1: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Add a contribution with fresh OS randomness and print its digest
+    Contribute {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Check every contribution and print their digests
+    Verify {
+        #[arg(long)]
+        depth: usize,
+        #[arg(long)]
+        phase1: PathBuf,
+        #[arg(long)]
+        params: PathBuf,
+    },
+    /// Verify, then write the final proving and verifying keys
+    Finalize {
+        #[arg(long)]
+        depth: usize,
+        #[arg(long)]
+        phase1: PathBuf,
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        pk: PathBuf,
+        #[arg(long)]
+        vk: PathBuf,
+    },
+    /// Write an insecure phase-1 transcript large enough for DEPTH
+    SamplePhase1 {
+        #[arg(long)]
+        depth: usize,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+/// Placeholder circuit for trees of `depth`, with the CRH parameters the
+/// membership CLI derives from `test_rng`.
+fn circuit(depth: usize) -> MerkleTreeCircuit<Curve> {
+    let mut rng = ark_std::test_rng();
+    let leaf_crh_params = <LeafHash<Curve> as CRH>::setup(&mut rng).unwrap();
+    let two_to_one_crh_params = <TwoToOneHash<Curve> as TwoToOneCRH>::setup(&mut rng).unwrap();
+    MerkleTreeCircuit::placeholder(leaf_crh_params, two_to_one_crh_params, depth)
+}
+
+fn os_rng() -> StdRng {
+    StdRng::from_seed(rand::random())
+}
+
+fn read_phase1(path: &Path) -> Result<Phase1<Bls12_381>, Box<dyn Error>> {
+    Ok(Phase1::read(
+        BufReader::new(File::open(path)?),
+        &mut os_rng(),
+    )?)
+}
+
+fn read_params(path: &Path) -> Result<Phase2<Bls12_381>, Box<dyn Error>> {
+    Ok(Phase2::deserialize(BufReader::new(File::open(path)?))?)
+}
+
+fn write<T: CanonicalSerialize>(value: &T, path: &Path) -> Result<(), Box<dyn Error>> {
+    value.serialize(BufWriter::new(File::create(path)?))?;
+    Ok(())
+}
+
+/// Verifies the parameters at `params` against the initial parameters this
+/// process derives itself.
+fn verify(depth: usize, phase1: &Path, params: &Path) -> Result<Phase2<Bls12_381>, Box<dyn Error>> {
+    let initial = Phase2::initialize(circuit(depth), &read_phase1(phase1)?)?;
+    let phase2 = read_params(params)?;
+    for (index, digest) in phase2.verify(&initial, &mut os_rng())?.iter().enumerate() {
+        println!("contribution {}: {}", index, hex::encode(digest));
+    }
+    Ok(phase2)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match Cli::parse().command {
+        Command::Init { depth, phase1, out } => {
+            let initial = Phase2::initialize(circuit(depth), &read_phase1(&phase1)?)?;
+            write(&initial, &out)?;
+            println!(
+                "initial parameters digest: {}",
+                hex::encode(initial.initial_digest())
+            );
+        }
+        Command::Contribute { params, out } => {
+            let mut phase2 = read_params(&params)?;
+            let digest = phase2.contribute(&mut os_rng());
+            write(&phase2, &out)?;
+            println!("contribution digest: {}", hex::encode(digest));
+        }
+        Command::Verify {
+            depth,
+            phase1,
+            params,
+        } => {
+            verify(depth, &phase1, &params)?;
+        }
+        Command::Finalize {
+            depth,
+            phase1,
+            params,
+            pk,
+            vk,
+        } => {
+            let (proving_key, verifying_key) = verify(depth, &phase1, &params)?.into_keys();
+            write(&proving_key, &pk)?;
+            write(&verifying_key, &vk)?;
+        }
+        Command::SamplePhase1 { depth, out } => {
+            let transcript =
+                Phase1::<Bls12_381>::sample(domain_size(circuit(depth))?, &mut os_rng());
+            transcript.write(BufWriter::new(File::create(out)?))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Multi-party Groth16 setup for membership circuits.
+//!
+//! A [`Phase1`] transcript (powers of `τ`, `α` and `β` from a public
+//! ceremony) is specialized to one circuit by [`Phase2::initialize`]. Each
+//! participant then loads the latest [`Phase2`], calls [`Phase2::contribute`]
+//! and publishes the result; anyone can [`Phase2::verify`] the chain of
+//! contributions before taking the final keys with [`Phase2::into_keys`].
+//!
+//! The `ceremony` binary runs every step as a separate process over files.
+
+mod phase1;
+mod phase2;
+
+pub use phase1::Phase1;
+pub use phase2::{Contribution, Phase2};
+
+use crate::powers_of_tau::TranscriptError;
+use ark_ff::PrimeField;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisError, SynthesisMode,
+};
+use ark_serialize::SerializationError;
+use std::fmt;
+
+/// Why a ceremony step failed.
+#[derive(Debug)]
+pub enum CeremonyError {
+    Synthesis(SynthesisError),
+    Serialization(SerializationError),
+    /// The phase-1 transcript is invalid or too short for the circuit.
+    Phase1(TranscriptError),
+    /// The parameters were not derived from the given initial parameters, i.e.
+    /// from another circuit or phase-1 transcript.
+    WrongInitialParameters,
+    /// The contribution at this index does not prove knowledge of its factor,
+    /// or does not build on the previous one.
+    InvalidContribution(usize),
+    /// The parameters do not match the last contribution.
+    InconsistentParameters,
+}
+
+impl fmt::Display for CeremonyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Synthesis(err) => write!(f, "circuit synthesis failed: {}", err),
+            Self::Serialization(err) => write!(f, "malformed parameters: {}", err),
+            Self::Phase1(err) => write!(f, "unusable phase-1 transcript: {}", err),
+            Self::WrongInitialParameters => {
+                write!(f, "parameters belong to another circuit or transcript")
+            }
+            Self::InvalidContribution(index) => write!(f, "contribution {} is invalid", index),
+            Self::InconsistentParameters => {
+                write!(f, "parameters do not match the last contribution")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CeremonyError {}
+
+impl From<SynthesisError> for CeremonyError {
+    fn from(err: SynthesisError) -> Self {
+        Self::Synthesis(err)
+    }
+}
+
+impl From<SerializationError> for CeremonyError {
+    fn from(err: SerializationError) -> Self {
+        Self::Serialization(err)
+    }
+}
+
+impl From<TranscriptError> for CeremonyError {
+    fn from(err: TranscriptError) -> Self {
+        Self::Phase1(err)
+    }
+}
+
+/// Size of the QAP domain of `circuit`, which the phase-1 transcript must
+/// cover (see [`Phase1::check_domain_size`]).
+pub fn domain_size<F: PrimeField, C: ConstraintSynthesizer<F>>(
+    circuit: C,
+) -> Result<usize, SynthesisError> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    cs.set_mode(SynthesisMode::Setup);
+    circuit.generate_constraints(cs.clone())?;
+    cs.finalize();
+
+    GeneralEvaluationDomain::<F>::new(cs.num_constraints() + cs.num_instance_variables())
+        .map(|domain| domain.size())
+        .ok_or(SynthesisError::PolynomialDegreeTooLarge)
+}
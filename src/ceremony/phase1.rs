@@ -0,0 +1,203 @@
+use crate::powers_of_tau::{
+    fixed_base_mul, powers, random_combination, PowersOfTau, TranscriptError,
+};
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::rand::RngCore;
+
+/// Phase-1 transcript for Groth16: a [`PowersOfTau`] transcript followed by
+///
+/// | section              | elements                 | count   |
+/// |----------------------|--------------------------|---------|
+/// | `beta_tau_powers_g1` | `β·τ^i · G1`, `i = 0..l` | `l ≥ 1` |
+/// | `beta_g2`            | `β · G2`                 | `1`     |
+///
+/// in the same encoding, the vector with its `u64` count and the single point
+/// without one. A circuit whose QAP domain has `n` elements needs `2n - 1`
+/// powers of `τ` in G1 and `n` of every other sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phase1<E: PairingEngine> {
+    powers: PowersOfTau<E>,
+    beta_tau_powers_g1: Vec<E::G1Affine>,
+    beta_g2: E::G2Affine,
+}
+
+impl<E: PairingEngine> Phase1<E> {
+    /// Reads and verifies a transcript. `rng` draws the randomizers of the
+    /// batched pairing checks.
+    pub fn read<R: Read, Rng: RngCore>(
+        mut reader: R,
+        rng: &mut Rng,
+    ) -> Result<Self, TranscriptError> {
+        let transcript = Self {
+            powers: PowersOfTau::read(&mut reader, rng)?,
+            beta_tau_powers_g1: Vec::deserialize(&mut reader)?,
+            beta_g2: E::G2Affine::deserialize(&mut reader)?,
+        };
+        transcript.verify_beta(rng)?;
+        Ok(transcript)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.powers.write(&mut writer)?;
+        self.beta_tau_powers_g1.serialize(&mut writer)?;
+        self.beta_g2.serialize(&mut writer)
+    }
+
+    /// Samples a transcript for QAP domains of up to `domain_size` elements.
+    ///
+    /// Whoever runs this knows `τ`, `α` and `β` and can forge proofs under any
+    /// keys derived from it, so it only suits tests and local trial ceremonies.
+    pub fn sample<R: RngCore>(domain_size: usize, rng: &mut R) -> Self {
+        let tau = E::Fr::rand(rng);
+        let beta = E::Fr::rand(rng);
+
+        let beta_tau_powers = powers(tau, domain_size)
+            .into_iter()
+            .map(|power| beta * power)
+            .collect::<Vec<_>>();
+        Self {
+            powers: PowersOfTau::from_secrets(
+                tau,
+                E::Fr::rand(rng),
+                2 * domain_size - 1,
+                domain_size,
+            ),
+            beta_tau_powers_g1: fixed_base_mul(
+                E::G1Projective::prime_subgroup_generator(),
+                &beta_tau_powers,
+            ),
+            beta_g2: E::G2Affine::prime_subgroup_generator()
+                .mul(beta)
+                .into_affine(),
+        }
+    }
+
+    /// Fails unless the transcript covers QAP domains of `domain_size` elements.
+    pub fn check_domain_size(&self, domain_size: usize) -> Result<(), TranscriptError> {
+        let sections = [
+            (
+                "tau_powers_g1",
+                self.tau_powers_g1().len(),
+                2 * domain_size - 1,
+            ),
+            ("tau_powers_g2", self.tau_powers_g2().len(), domain_size),
+            (
+                "alpha_tau_powers_g1",
+                self.alpha_tau_powers_g1().len(),
+                domain_size,
+            ),
+            (
+                "beta_tau_powers_g1",
+                self.beta_tau_powers_g1.len(),
+                domain_size,
+            ),
+        ];
+        for (section, found, required) in sections {
+            if found < required {
+                return Err(TranscriptError::TooFewPowers {
+                    section,
+                    required,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn tau_powers_g1(&self) -> &[E::G1Affine] {
+        self.powers.tau_powers_g1()
+    }
+
+    pub(crate) fn tau_powers_g2(&self) -> &[E::G2Affine] {
+        self.powers.tau_powers_g2()
+    }
+
+    pub(crate) fn alpha_tau_powers_g1(&self) -> &[E::G1Affine] {
+        self.powers.alpha_tau_powers_g1()
+    }
+
+    pub(crate) fn beta_tau_powers_g1(&self) -> &[E::G1Affine] {
+        &self.beta_tau_powers_g1
+    }
+
+    pub(crate) fn beta_g2(&self) -> E::G2Affine {
+        self.beta_g2
+    }
+
+    /// Checks the `β` sections against the already verified powers of `τ`.
+    fn verify_beta<R: RngCore>(&self, rng: &mut R) -> Result<(), TranscriptError> {
+        if self.beta_tau_powers_g1.is_empty() {
+            return Err(TranscriptError::TooFewPowers {
+                section: "beta_tau_powers_g1",
+                required: 1,
+                found: 0,
+            });
+        }
+
+        let g1 = self.tau_powers_g1()[0];
+        let (g2, tau_g2) = (self.tau_powers_g2()[0], self.tau_powers_g2()[1]);
+        let beta_g1 = self.beta_tau_powers_g1[0];
+        if beta_g1.is_zero() || beta_g1 == g1 {
+            return Err(TranscriptError::Degenerate);
+        }
+        if E::pairing(beta_g1, g2) != E::pairing(g1, self.beta_g2) {
+            return Err(TranscriptError::InconsistentPowers("beta_g2"));
+        }
+
+        let points = &self.beta_tau_powers_g1;
+        let (lhs, rhs) = random_combination(&points[1..], &points[..points.len() - 1], rng);
+        if E::pairing(lhs, g2) != E::pairing(rhs, tau_g2) {
+            return Err(TranscriptError::InconsistentPowers("beta_tau_powers_g1"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+
+    fn encode(transcript: &Phase1<Bls12_381>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        transcript.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn reads_sampled_transcript() {
+        let mut rng = ark_std::test_rng();
+        let transcript = Phase1::<Bls12_381>::sample(16, &mut rng);
+
+        let read = Phase1::<Bls12_381>::read(&*encode(&transcript), &mut rng).unwrap();
+        assert_eq!(read, transcript);
+        assert!(read.check_domain_size(16).is_ok());
+        assert!(matches!(
+            read.check_domain_size(32),
+            Err(TranscriptError::TooFewPowers { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_foreign_beta() {
+        let mut rng = ark_std::test_rng();
+        let transcript = Phase1::<Bls12_381>::sample(16, &mut rng);
+        let other = Phase1::<Bls12_381>::sample(16, &mut rng);
+
+        let mut tampered = transcript.clone();
+        tampered.beta_g2 = other.beta_g2;
+        assert!(matches!(
+            Phase1::<Bls12_381>::read(&*encode(&tampered), &mut rng),
+            Err(TranscriptError::InconsistentPowers("beta_g2"))
+        ));
+
+        let mut tampered = transcript;
+        tampered.beta_tau_powers_g1[3] = other.beta_tau_powers_g1[3];
+        assert!(matches!(
+            Phase1::<Bls12_381>::read(&*encode(&tampered), &mut rng),
+            Err(TranscriptError::InconsistentPowers("beta_tau_powers_g1"))
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+use super::{CeremonyError, Phase1};
+use crate::powers_of_tau::random_combination;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisError, SynthesisMode,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::rand::{CryptoRng, RngCore};
+use blake2::{Blake2s, Digest};
+
+/// Domain separator of the contributions' Fiat-Shamir challenges.
+const CHALLENGE_DOMAIN: &[u8] = b"zkmember-groth16-phase2";
+
+/// One participant's contribution: it multiplies `δ` by a secret factor `δ'`
+/// and proves knowledge of `δ'` with a Schnorr proof over G1.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: PairingEngine> {
+    /// `δ · G1` and `δ · G2` after the contribution.
+    delta_g1: E::G1Affine,
+    delta_g2: E::G2Affine,
+    /// `δ' · G1`.
+    public_key: E::G1Affine,
+    /// `k · G1` for the prover's nonce `k`.
+    commitment: E::G1Affine,
+    /// `k + c·δ'` for the challenge `c`.
+    response: E::Fr,
+}
+
+impl<E: PairingEngine> Contribution<E> {
+    /// Blake2s hash of the contribution, which its participant publishes so
+    /// that anyone can check it made it into the final parameters.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        self.serialize(&mut bytes).unwrap();
+        Blake2s::digest(&bytes).into()
+    }
+}
+
+/// Groth16 parameters of one circuit, together with the contributions that
+/// randomized their `δ` so far.
+///
+/// Phase 1 fixes `τ`, `α` and `β`; phase 2 only re-randomizes `δ` (`γ` stays
+/// one). The final keys are sound as long as one participant discarded their
+/// factor `δ'`.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Phase2<E: PairingEngine> {
+    /// Blake2s hash of the initial parameters, which binds every contribution
+    /// to the circuit and phase-1 transcript they were derived from.
+    circuit_digest: Vec<u8>,
+    params: ProvingKey<E>,
+    contributions: Vec<Contribution<E>>,
+}
+
+impl<E: PairingEngine> Phase2<E> {
+    /// Derives the initial parameters of `circuit` from `phase1`, with `δ = 1`.
+    /// This is deterministic, so every verifier recomputes it for themselves.
+    pub fn initialize<C: ConstraintSynthesizer<E::Fr>>(
+        circuit: C,
+        phase1: &Phase1<E>,
+    ) -> Result<Self, CeremonyError> {
+        let cs = ConstraintSystem::new_ref();
+        cs.set_optimization_goal(OptimizationGoal::Constraints);
+        cs.set_mode(SynthesisMode::Setup);
+        circuit.generate_constraints(cs.clone())?;
+        cs.finalize();
+
+        // The same QAP as ark-groth16's reduction: one row per constraint, then
+        // one per instance variable enforcing it in the A polynomials
+        let num_constraints = cs.num_constraints();
+        let num_instance_variables = cs.num_instance_variables();
+        let num_variables = num_instance_variables + cs.num_witness_variables();
+        let domain =
+            GeneralEvaluationDomain::<E::Fr>::new(num_constraints + num_instance_variables)
+                .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let n = domain.size();
+        phase1.check_domain_size(n)?;
+
+        // Lagrange polynomials at τ, in the exponent
+        let lagrange_g1 = lagrange(&domain, &phase1.tau_powers_g1()[..n]);
+        let lagrange_g2 = lagrange(&domain, &phase1.tau_powers_g2()[..n]);
+        let alpha_lagrange_g1 = lagrange(&domain, &phase1.alpha_tau_powers_g1()[..n]);
+        let beta_lagrange_g1 = lagrange(&domain, &phase1.beta_tau_powers_g1()[..n]);
+
+        // u_i(τ), v_i(τ) and β·u_i(τ) + α·v_i(τ) + w_i(τ) of every variable
+        let mut a_g1 = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g1 = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g2 = vec![E::G2Projective::zero(); num_variables];
+        let mut abc_g1 = vec![E::G1Projective::zero(); num_variables];
+
+        for i in 0..num_instance_variables {
+            a_g1[i] += &lagrange_g1[num_constraints + i];
+            abc_g1[i] += &beta_lagrange_g1[num_constraints + i];
+        }
+
+        let matrices = cs
+            .to_matrices()
+            .expect("constraint system is in setup mode");
+        for row in 0..num_constraints {
+            for (coeff, index) in &matrices.a[row] {
+                a_g1[*index] += &scale(lagrange_g1[row], coeff);
+                abc_g1[*index] += &scale(beta_lagrange_g1[row], coeff);
+            }
+            for (coeff, index) in &matrices.b[row] {
+                b_g1[*index] += &scale(lagrange_g1[row], coeff);
+                b_g2[*index] += &scale(lagrange_g2[row], coeff);
+                abc_g1[*index] += &scale(alpha_lagrange_g1[row], coeff);
+            }
+            for (coeff, index) in &matrices.c[row] {
+                abc_g1[*index] += &scale(lagrange_g1[row], coeff);
+            }
+        }
+
+        // τ^i · t(τ) for the vanishing polynomial t(X) = X^n - 1
+        let tau_powers_g1 = phase1.tau_powers_g1();
+        let h_query = (0..n - 1)
+            .map(|i| tau_powers_g1[i + n].into_projective() - tau_powers_g1[i].into_projective())
+            .collect::<Vec<_>>();
+
+        let l_query = abc_g1.split_off(num_instance_variables);
+        let params = ProvingKey {
+            vk: VerifyingKey {
+                alpha_g1: phase1.alpha_tau_powers_g1()[0],
+                beta_g2: phase1.beta_g2(),
+                gamma_g2: E::G2Affine::prime_subgroup_generator(),
+                delta_g2: E::G2Affine::prime_subgroup_generator(),
+                gamma_abc_g1: E::G1Projective::batch_normalization_into_affine(&abc_g1),
+            },
+            beta_g1: phase1.beta_tau_powers_g1()[0],
+            delta_g1: E::G1Affine::prime_subgroup_generator(),
+            a_query: E::G1Projective::batch_normalization_into_affine(&a_g1),
+            b_g1_query: E::G1Projective::batch_normalization_into_affine(&b_g1),
+            b_g2_query: E::G2Projective::batch_normalization_into_affine(&b_g2),
+            h_query: E::G1Projective::batch_normalization_into_affine(&h_query),
+            l_query: E::G1Projective::batch_normalization_into_affine(&l_query),
+        };
+
+        let mut bytes = Vec::new();
+        params.serialize(&mut bytes)?;
+        Ok(Self {
+            circuit_digest: Blake2s::digest(&bytes).to_vec(),
+            params,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// Blake2s hash of the initial parameters, which the coordinator publishes
+    /// so that participants can check which circuit they contribute to.
+    pub fn initial_digest(&self) -> &[u8] {
+        &self.circuit_digest
+    }
+
+    pub fn contributions(&self) -> &[Contribution<E>] {
+        &self.contributions
+    }
+
+    /// Multiplies `δ` by a fresh secret factor and records the contribution,
+    /// whose [digest](Contribution::digest) is returned. The factor is dropped
+    /// when this returns.
+    pub fn contribute<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> [u8; 32] {
+        let delta = loop {
+            let delta = E::Fr::rand(rng);
+            if !delta.is_zero() {
+                break delta;
+            }
+        };
+        let nonce = E::Fr::rand(rng);
+
+        let generator = E::G1Affine::prime_subgroup_generator();
+        let public_key = generator.mul(delta).into_affine();
+        let commitment = generator.mul(nonce).into_affine();
+        let challenge = self.challenge(&public_key, &commitment);
+
+        let delta_inverse = delta.inverse().unwrap();
+        self.params.delta_g1 = self.params.delta_g1.mul(delta).into_affine();
+        self.params.vk.delta_g2 = self.params.vk.delta_g2.mul(delta).into_affine();
+        self.params.h_query = scale_all(&self.params.h_query, delta_inverse);
+        self.params.l_query = scale_all(&self.params.l_query, delta_inverse);
+
+        let contribution = Contribution {
+            delta_g1: self.params.delta_g1,
+            delta_g2: self.params.vk.delta_g2,
+            public_key,
+            commitment,
+            response: nonce + challenge * delta,
+        };
+        let digest = contribution.digest();
+        self.contributions.push(contribution);
+        digest
+    }
+
+    /// Checks that `self` was derived from `initial` by its recorded
+    /// contributions, and returns their digests in order.
+    ///
+    /// `initial` must be computed by the verifier with [`Self::initialize`]
+    /// rather than taken from the ceremony. `rng` draws the randomizers of the
+    /// batched pairing checks.
+    pub fn verify<R: RngCore>(
+        &self,
+        initial: &Self,
+        rng: &mut R,
+    ) -> Result<Vec<[u8; 32]>, CeremonyError> {
+        let (params, start) = (&self.params, &initial.params);
+        if !initial.contributions.is_empty()
+            || self.circuit_digest != initial.circuit_digest
+            || params.vk.alpha_g1 != start.vk.alpha_g1
+            || params.vk.beta_g2 != start.vk.beta_g2
+            || params.vk.gamma_g2 != start.vk.gamma_g2
+            || params.vk.gamma_abc_g1 != start.vk.gamma_abc_g1
+            || params.beta_g1 != start.beta_g1
+            || params.a_query != start.a_query
+            || params.b_g1_query != start.b_g1_query
+            || params.b_g2_query != start.b_g2_query
+            || params.h_query.len() != start.h_query.len()
+            || params.l_query.len() != start.l_query.len()
+        {
+            return Err(CeremonyError::WrongInitialParameters);
+        }
+
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        let mut verifier = Self {
+            circuit_digest: self.circuit_digest.clone(),
+            params: start.clone(),
+            contributions: Vec::with_capacity(self.contributions.len()),
+        };
+        let (mut delta_g1, mut delta_g2) = (start.delta_g1, start.vk.delta_g2);
+        for (index, contribution) in self.contributions.iter().enumerate() {
+            let challenge = verifier.challenge(&contribution.public_key, &contribution.commitment);
+            let knows_factor = g1.mul(contribution.response)
+                == contribution.commitment.into_projective()
+                    + contribution.public_key.mul(challenge);
+            // δ·G2 was multiplied by the proven factor, and δ·G1 along with it
+            let extends_delta = !contribution.public_key.is_zero()
+                && E::pairing(contribution.public_key, delta_g2)
+                    == E::pairing(g1, contribution.delta_g2)
+                && E::pairing(contribution.delta_g1, g2) == E::pairing(g1, contribution.delta_g2);
+            if !knows_factor || !extends_delta {
+                return Err(CeremonyError::InvalidContribution(index));
+            }
+
+            delta_g1 = contribution.delta_g1;
+            delta_g2 = contribution.delta_g2;
+            verifier.contributions.push(contribution.clone());
+        }
+
+        // The queries divided by δ were divided by the same accumulated factor
+        if params.delta_g1 != delta_g1 || params.vk.delta_g2 != delta_g2 {
+            return Err(CeremonyError::InconsistentParameters);
+        }
+        for (current, initial) in [
+            (&params.h_query, &start.h_query),
+            (&params.l_query, &start.l_query),
+        ] {
+            let (lhs, rhs) = random_combination(current, initial, rng);
+            if E::pairing(lhs, delta_g2) != E::pairing(rhs, g2) {
+                return Err(CeremonyError::InconsistentParameters);
+            }
+        }
+
+        Ok(self
+            .contributions
+            .iter()
+            .map(Contribution::digest)
+            .collect())
+    }
+
+    /// Final keys of the ceremony. Only meaningful once [`Self::verify`] passed.
+    pub fn into_keys(self) -> (ProvingKey<E>, VerifyingKey<E>) {
+        let vk = self.params.vk.clone();
+        (self.params, vk)
+    }
+
+    /// Fiat-Shamir challenge of the next contribution, over the circuit, every
+    /// earlier contribution and the new proof's public key and commitment.
+    fn challenge(&self, public_key: &E::G1Affine, commitment: &E::G1Affine) -> E::Fr {
+        let mut bytes = CHALLENGE_DOMAIN.to_vec();
+        bytes.extend_from_slice(&self.circuit_digest);
+        self.contributions.serialize(&mut bytes).unwrap();
+        public_key.serialize(&mut bytes).unwrap();
+        commitment.serialize(&mut bytes).unwrap();
+        E::Fr::from_le_bytes_mod_order(&Blake2s::digest(&bytes))
+    }
+}
+
+/// `[L_i(τ) · G]` for the Lagrange polynomials `L_i` of `domain`, given
+/// `[τ^i · G]`.
+fn lagrange<G: AffineCurve>(
+    domain: &GeneralEvaluationDomain<G::ScalarField>,
+    tau_powers: &[G],
+) -> Vec<G::Projective> {
+    let points = tau_powers
+        .iter()
+        .map(AffineCurve::into_projective)
+        .collect::<Vec<_>>();
+    domain.ifft(&points)
+}
+
+fn scale<G: ProjectiveCurve>(point: G, coeff: &G::ScalarField) -> G {
+    if coeff.is_one() {
+        point
+    } else {
+        point.mul(coeff.into_repr())
+    }
+}
+
+fn scale_all<G: AffineCurve>(points: &[G], factor: G::ScalarField) -> Vec<G> {
+    let scaled = points
+        .iter()
+        .map(|point| point.mul(factor))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&scaled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ceremony::domain_size;
+    use crate::commitments::pedersen::{
+        new_membership_tree, LeafHash, MerkleTreeCircuit, TwoToOneHash,
+    };
+    use crate::commitments::pedersen381::EdOnBls12_381;
+    use crate::member::generate_members;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH, SNARK};
+    use ark_groth16::Groth16;
+    use ark_relations::{
+        lc,
+        r1cs::{ConstraintSystemRef, Variable},
+    };
+
+    /// `x · y = z` and `x · x = w` for public `z` and `w`. Membership circuits
+    /// take minutes to initialize without parallelism beyond small depths, so
+    /// most of the ceremony is exercised on this one.
+    #[derive(Clone)]
+    struct ProductCircuit {
+        x: Fr,
+        y: Fr,
+    }
+
+    impl ConstraintSynthesizer<Fr> for ProductCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let z = cs.new_input_variable(|| Ok(self.x * self.y))?;
+            let w = cs.new_input_variable(|| Ok(self.x * self.x))?;
+            let x = cs.new_witness_variable(|| Ok(self.x))?;
+            let y = cs.new_witness_variable(|| Ok(self.y))?;
+            cs.enforce_constraint(lc!() + x, lc!() + y, lc!() + z)?;
+            cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + w)?;
+            cs.enforce_constraint(lc!() + x + y, lc!() + Variable::One, lc!() + x + y)
+        }
+    }
+
+    fn encode(phase2: &Phase2<Bls12_381>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        phase2.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn ceremony_keys_prove() {
+        let mut rng = ark_std::test_rng();
+        let circuit = ProductCircuit {
+            x: Fr::from(3u64),
+            y: Fr::from(5u64),
+        };
+
+        let phase1 = Phase1::<Bls12_381>::sample(domain_size(circuit.clone()).unwrap(), &mut rng);
+        let initial = Phase2::initialize(circuit.clone(), &phase1).unwrap();
+
+        // Every participant only sees the encoding the previous one published
+        let mut bytes = encode(&initial);
+        let mut digests = Vec::new();
+        for _ in 0..3 {
+            let mut phase2 = Phase2::<Bls12_381>::deserialize(&*bytes).unwrap();
+            digests.push(phase2.contribute(&mut rng));
+            bytes = encode(&phase2);
+        }
+        let phase2 = Phase2::<Bls12_381>::deserialize(&*bytes).unwrap();
+        assert_eq!(phase2.verify(&initial, &mut rng).unwrap(), digests);
+
+        let (pk, vk) = phase2.into_keys();
+        let proof = Groth16::<Bls12_381>::prove(&pk, circuit, &mut rng).unwrap();
+        assert!(
+            Groth16::<Bls12_381>::verify(&vk, &[Fr::from(15u64), Fr::from(9u64)], &proof).unwrap()
+        );
+        assert!(
+            !Groth16::<Bls12_381>::verify(&vk, &[Fr::from(16u64), Fr::from(9u64)], &proof).unwrap()
+        );
+    }
+
+    #[test]
+    fn membership_ceremony_at_depth_two() {
+        let mut rng = ark_std::test_rng();
+        let leaf_crh_params = <LeafHash<EdOnBls12_381> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params =
+            <TwoToOneHash<EdOnBls12_381> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let placeholder = MerkleTreeCircuit::<EdOnBls12_381>::placeholder(
+            leaf_crh_params.clone(),
+            two_to_one_crh_params.clone(),
+            2,
+        );
+        let phase1 =
+            Phase1::<Bls12_381>::sample(domain_size(placeholder.clone()).unwrap(), &mut rng);
+        let initial = Phase2::initialize(placeholder, &phase1).unwrap();
+
+        let mut phase2 = initial.clone();
+        let digests = vec![phase2.contribute(&mut rng), phase2.contribute(&mut rng)];
+        assert_eq!(phase2.verify(&initial, &mut rng).unwrap(), digests);
+
+        let mut members = Box::default();
+        generate_members(&mut members, 4);
+        let mut leaves = members
+            .iter()
+            .map(|member| {
+                member
+                    .hash::<LeafHash<EdOnBls12_381>>(&leaf_crh_params)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<EdOnBls12_381>(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &mut leaves,
+        );
+        let circuit = MerkleTreeCircuit::<EdOnBls12_381> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: tree.root(),
+            leaf_hash: leaves[2],
+            authentication_path: Some(tree.generate_proof(2).unwrap()),
+        };
+
+        let (pk, vk) = phase2.into_keys();
+        let proof = Groth16::<Bls12_381>::prove(&pk, circuit, &mut rng).unwrap();
+        assert!(Groth16::<Bls12_381>::verify(&vk, &[tree.root(), leaves[2]], &proof).unwrap());
+        assert!(!Groth16::<Bls12_381>::verify(&vk, &[tree.root(), leaves[1]], &proof).unwrap());
+    }
+
+    #[test]
+    fn rejects_broken_chains() {
+        let mut rng = ark_std::test_rng();
+        let circuit = ProductCircuit {
+            x: Fr::from(3u64),
+            y: Fr::from(5u64),
+        };
+
+        let phase1 = Phase1::<Bls12_381>::sample(domain_size(circuit.clone()).unwrap(), &mut rng);
+        let initial = Phase2::initialize(circuit.clone(), &phase1).unwrap();
+        let mut phase2 = initial.clone();
+        for _ in 0..3 {
+            phase2.contribute(&mut rng);
+        }
+
+        // A dropped contribution breaks the chain of δ
+        let mut tampered = phase2.clone();
+        tampered.contributions.remove(1);
+        assert!(matches!(
+            tampered.verify(&initial, &mut rng),
+            Err(CeremonyError::InvalidContribution(1))
+        ));
+
+        // So does a contribution without knowledge of its factor
+        let mut tampered = phase2.clone();
+        tampered.contributions[2].response += Fr::one();
+        assert!(matches!(
+            tampered.verify(&initial, &mut rng),
+            Err(CeremonyError::InvalidContribution(2))
+        ));
+
+        // Queries not rescaled along with δ
+        let mut tampered = phase2.clone();
+        tampered.params.l_query[0] = initial.params.l_query[0];
+        assert!(matches!(
+            tampered.verify(&initial, &mut rng),
+            Err(CeremonyError::InconsistentParameters)
+        ));
+
+        // Parameters derived from another phase-1 transcript
+        let other = Phase2::initialize(
+            circuit.clone(),
+            &Phase1::sample(domain_size(circuit).unwrap(), &mut rng),
+        )
+        .unwrap();
+        assert!(matches!(
+            phase2.verify(&other, &mut rng),
+            Err(CeremonyError::WrongInitialParameters)
+        ));
+    }
+}
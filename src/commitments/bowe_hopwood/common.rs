@@ -0,0 +1,154 @@
+use super::{BoweHopwoodCurve, WINDOW_SIZE};
+use crate::crh::bowe_hopwood::{
+    constraints::BoweHopwoodCRHCompressorGadget, BoweHopwoodCRHCompressor,
+};
+use ark_crypto_primitives::crh::constraints::{CRHGadget, TwoToOneCRHGadget};
+use ark_crypto_primitives::crh::{pedersen, TwoToOneCRH};
+use ark_crypto_primitives::merkle_tree::Config;
+use ark_crypto_primitives::{MerkleTree, Path, CRH};
+use core::marker::PhantomData;
+
+/////////////////////////////
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TwoToOneWindow<C>(PhantomData<C>);
+impl<C: BoweHopwoodCurve> pedersen::Window for TwoToOneWindow<C> {
+    const WINDOW_SIZE: usize = WINDOW_SIZE;
+    const NUM_WINDOWS: usize = C::TWO_TO_ONE_NUM_WINDOWS;
+}
+
+pub type TwoToOneHash<C> =
+    BoweHopwoodCRHCompressor<<C as BoweHopwoodCurve>::Parameters, TwoToOneWindow<C>>;
+pub type Root<C> = <TwoToOneHash<C> as TwoToOneCRH>::Output;
+
+pub type TwoToOneHashGadget<C> = BoweHopwoodCRHCompressorGadget<<C as BoweHopwoodCurve>::Field>;
+
+/////////////////////////////
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct LeafWindow<C>(PhantomData<C>);
+impl<C: BoweHopwoodCurve> pedersen::Window for LeafWindow<C> {
+    const WINDOW_SIZE: usize = WINDOW_SIZE;
+    const NUM_WINDOWS: usize = C::LEAF_NUM_WINDOWS;
+}
+
+pub type LeafHash<C> = BoweHopwoodCRHCompressor<<C as BoweHopwoodCurve>::Parameters, LeafWindow<C>>;
+pub type Leaf<C> = <LeafHash<C> as CRH>::Output;
+
+pub type LeafHashGadget<C> = BoweHopwoodCRHCompressorGadget<<C as BoweHopwoodCurve>::Field>;
+
+/////////////////////////////
+
+pub type LeafHashParamsVar<C> =
+    <LeafHashGadget<C> as CRHGadget<LeafHash<C>, <C as BoweHopwoodCurve>::Field>>::ParametersVar;
+
+pub type TwoToOneHashParamsVar<C> = <TwoToOneHashGadget<C> as TwoToOneCRHGadget<
+    TwoToOneHash<C>,
+    <C as BoweHopwoodCurve>::Field,
+>>::ParametersVar;
+
+/////////////////////////////
+
+#[derive(Clone)]
+pub struct MerkleConfig<C>(PhantomData<C>);
+impl<C: BoweHopwoodCurve> Config for MerkleConfig<C> {
+    type LeafHash = LeafHash<C>;
+    type TwoToOneHash = TwoToOneHash<C>;
+}
+
+pub type MerklePath<C> = Path<MerkleConfig<C>>;
+
+pub type MembershipTree<C> = MerkleTree<MerkleConfig<C>>;
+
+pub fn new_membership_tree<C: BoweHopwoodCurve>(
+    leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    leaves: &mut Vec<C::Field>,
+) -> MembershipTree<C> {
+    clean_membership_list::<C>(leaves);
+    MembershipTree::<C>::new(leaf_crh_params, two_to_one_crh_params, leaves.as_ref()).unwrap()
+}
+
+/// Pads `leaves` to a power of two, and to at least two, with
+/// [`empty_leaf`](crate::commitments::empty_leaf).
+fn clean_membership_list<C: BoweHopwoodCurve>(leaves: &mut Vec<C::Field>) {
+    let num_members = leaves.len();
+
+    let num_needed = if num_members == 1 {
+        1
+    } else {
+        num_members.next_power_of_two() - num_members
+    };
+
+    leaves.append(&mut vec![
+        crate::commitments::empty_leaf::<C::Field>();
+        num_needed
+    ]);
+}
+
+#[cfg(test)]
+mod membership_tree_tests {
+    use super::*;
+    use crate::member::Member;
+
+    fn check_three_leaves<C: BoweHopwoodCurve>() {
+        let mut rng = ark_std::test_rng();
+        let params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+
+        let members = [Member::default(), Member::default(), Member::default()];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&params).unwrap())
+            .collect::<Vec<_>>();
+
+        clean_membership_list::<C>(&mut leaves);
+        assert_eq!(leaves.len(), 4); // Should add 1 more element to make it a power of two
+    }
+
+    fn check_merkle_tree<C: BoweHopwoodCurve>() {
+        let mut rng = ark_std::test_rng();
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("2".into(), "2@usc.edu".into(), None),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let path: MerklePath<C> = tree.generate_proof(2).unwrap();
+
+        assert!(path
+            .verify(
+                &leaf_crh_params,
+                &two_to_one_crh_params,
+                &tree.root(),
+                &members[2].hash::<LeafHash<C>>(&leaf_crh_params).unwrap(),
+            )
+            .unwrap());
+    }
+
+    fn check_record_fits<C: BoweHopwoodCurve>() {
+        let mut rng = ark_std::test_rng();
+        let params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+
+        // The longest record the Pedersen backends accept hashes here as well
+        let max = crate::commitments::pedersen381::MEMBER_RECORD_BYTES;
+        let member = (1..)
+            .map(|len| Member::new("jonathan".into(), "j".repeat(len), None))
+            .find(|member| member.to_bytes().unwrap().len() == max)
+            .unwrap();
+        assert!(member.hash::<LeafHash<C>>(&params).is_ok());
+    }
+
+    all_curves_tests! {
+        three_leaves => check_three_leaves,
+        merkle_tree => check_merkle_tree,
+        record_fits => check_record_fits,
+    }
+}
@@ -0,0 +1,72 @@
+use super::common::{LeafHash, LeafHashGadget, MerkleConfig, TwoToOneHash, TwoToOneHashGadget};
+use super::BoweHopwoodCurve;
+use crate::commitments::GenericMerkleTreeCircuit;
+use ark_crypto_primitives::{
+    crh::{CRHGadget, TwoToOneCRHGadget},
+    PathVar,
+};
+
+/// R1CS representation of the Merkle tree root.
+pub type BoweHopwoodRootVar<C> = <TwoToOneHashGadget<C> as TwoToOneCRHGadget<
+    TwoToOneHash<C>,
+    <C as BoweHopwoodCurve>::Field,
+>>::OutputVar;
+
+pub type BoweHopwoodLeafVar<C> =
+    <LeafHashGadget<C> as CRHGadget<LeafHash<C>, <C as BoweHopwoodCurve>::Field>>::OutputVar;
+
+/// R1CS representation of the Merkle tree path.
+pub type BoweHopwoodPathVar<C> = PathVar<
+    MerkleConfig<C>,
+    LeafHashGadget<C>,
+    TwoToOneHashGadget<C>,
+    <C as BoweHopwoodCurve>::Field,
+>;
+
+pub type MerkleTreeCircuit<C> = GenericMerkleTreeCircuit<C>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::bowe_hopwood::common::new_membership_tree;
+    use crate::member::Member;
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+
+    fn check_merkle_tree_constraints<C: BoweHopwoodCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("2".into(), "2@usc.edu".into(), None),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        // The path of leaf 1 only opens leaf 1
+        for (leaf, expected) in [(leaves[1], true), (leaves[2], false)] {
+            let circuit = MerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                leaf_hash: leaf,
+                authentication_path: Some(tree.generate_proof(1).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+
+    all_curves_tests! {
+        merkle_tree_constraints => check_merkle_tree_constraints,
+    }
+}
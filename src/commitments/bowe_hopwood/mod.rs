@@ -0,0 +1,114 @@
+//! Bowe–Hopwood Pedersen backend, generic over the embedded curve like the
+//! [`pedersen`](super::pedersen) backend. Its only circuit is the
+//! [`GenericMerkleTreeCircuit`](super::GenericMerkleTreeCircuit).
+
+/// Expands each `test => check` pair into a test that runs the curve-generic
+/// `check` for every [`BoweHopwoodCurve`] of the crate.
+#[cfg(test)]
+macro_rules! all_curves_tests {
+    ($($test:ident => $check:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                $check::<crate::commitments::bowe_hopwood381::EdOnBls12_381>();
+                $check::<crate::commitments::bowe_hopwood761::EdOnBw6_761>();
+            }
+        )*
+    };
+}
+
+pub mod common;
+pub mod constraint;
+
+pub use common::*;
+pub use constraint::*;
+
+use super::MerkleBackend;
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::PrimeField;
+use core::fmt::Debug;
+use core::hash::Hash;
+
+/// Chunks per Bowe–Hopwood window, shared by the leaf and two-to-one hashes.
+/// Each window absorbs `WINDOW_SIZE * CHUNK_SIZE` bits.
+pub const WINDOW_SIZE: usize = 63;
+
+/// Curve choice of the Bowe–Hopwood backend: the twisted Edwards curve the
+/// generators live on, the pairing curve whose scalar field is that curve's
+/// base field, and the number of windows of each hash.
+///
+/// Implementors are marker types such as
+/// [`EdOnBls12_381`](super::bowe_hopwood381::EdOnBls12_381).
+pub trait BoweHopwoodCurve: Clone + Copy + Debug + Default + PartialEq + Eq + Hash {
+    /// Embedded twisted Edwards curve.
+    type Parameters: TEModelParameters<BaseField = Self::Field>;
+    /// Circuit field: the base field of the Edwards curve.
+    type Field: PrimeField;
+    /// Pairing curve proofs are created with.
+    type Pairing: PairingEngine<Fr = Self::Field>;
+
+    /// Number of windows of the leaf hash. It caps the length of a serialized
+    /// [`Member`](crate::member::Member) record, so it must cover at least the
+    /// records every other backend accepts.
+    const LEAF_NUM_WINDOWS: usize;
+    /// Number of windows of the two-to-one hash. Both children are serialized
+    /// field elements, so it must cover twice their width.
+    const TWO_TO_ONE_NUM_WINDOWS: usize;
+}
+
+impl<C: BoweHopwoodCurve> MerkleBackend for C {
+    type Field = C::Field;
+    type LeafHash = LeafHash<C>;
+    type TwoToOneHash = TwoToOneHash<C>;
+    type MerkleConfig = MerkleConfig<C>;
+    type LeafHashGadget = LeafHashGadget<C>;
+    type TwoToOneHashGadget = TwoToOneHashGadget<C>;
+}
+
+#[cfg(test)]
+mod groth16_tests {
+    use super::{new_membership_tree, BoweHopwoodCurve, LeafHash, MerkleTreeCircuit, TwoToOneHash};
+    use crate::member::Member;
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH, SNARK};
+    use ark_groth16::Groth16;
+
+    fn check_groth16_snark<C: BoweHopwoodCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+            Member::new("carol".into(), "carol@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let circuit = MerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params,
+            root,
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) = Groth16::<C::Pairing>::circuit_specific_setup(circuit.clone(), &mut rng)
+            .expect("setup failed");
+        let proof = Groth16::<C::Pairing>::prove(&pk, circuit, &mut rng).unwrap();
+
+        let verified = Groth16::<C::Pairing>::verify(&vk, &[root, leaves[1]], &proof)
+            .expect("verification failed");
+        assert!(verified, "SNARK proof verification failed");
+    }
+
+    all_curves_tests! {
+        test_groth16_snark => check_groth16_snark,
+    }
+}
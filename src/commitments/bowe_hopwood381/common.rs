@@ -0,0 +1,37 @@
+use super::EdOnBls12_381;
+use crate::commitments::bowe_hopwood::{common as generic, BoweHopwoodCurve};
+use ark_crypto_primitives::crh::TwoToOneCRH;
+use ark_crypto_primitives::CRH;
+
+/////////////////////////////
+
+pub type BoweHopwood381Field = <EdOnBls12_381 as BoweHopwoodCurve>::Field;
+
+/////////////////////////////
+
+pub type TwoToOneWindow = generic::TwoToOneWindow<EdOnBls12_381>;
+pub type TwoToOneHash = generic::TwoToOneHash<EdOnBls12_381>;
+pub type Root = generic::Root<EdOnBls12_381>;
+pub type TwoToOneHashGadget = generic::TwoToOneHashGadget<EdOnBls12_381>;
+
+pub type LeafWindow = generic::LeafWindow<EdOnBls12_381>;
+pub type LeafHash = generic::LeafHash<EdOnBls12_381>;
+pub type Leaf = generic::Leaf<EdOnBls12_381>;
+pub type LeafHashGadget = generic::LeafHashGadget<EdOnBls12_381>;
+
+pub type LeafHashParamsVar = generic::LeafHashParamsVar<EdOnBls12_381>;
+pub type TwoToOneHashParamsVar = generic::TwoToOneHashParamsVar<EdOnBls12_381>;
+
+/////////////////////////////
+
+pub type MerkleConfig = generic::MerkleConfig<EdOnBls12_381>;
+pub type MerklePath = generic::MerklePath<EdOnBls12_381>;
+pub type MembershipTree = generic::MembershipTree<EdOnBls12_381>;
+
+pub fn new_membership_tree(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as TwoToOneCRH>::Parameters,
+    leaves: &mut Vec<BoweHopwood381Field>,
+) -> MembershipTree {
+    generic::new_membership_tree::<EdOnBls12_381>(leaf_crh_params, two_to_one_crh_params, leaves)
+}
@@ -0,0 +1,12 @@
+use super::EdOnBls12_381;
+use crate::commitments::bowe_hopwood::constraint as generic;
+
+/// R1CS representation of the Merkle tree root.
+pub type BoweHopwoodRootVar = generic::BoweHopwoodRootVar<EdOnBls12_381>;
+
+pub type BoweHopwoodLeafVar = generic::BoweHopwoodLeafVar<EdOnBls12_381>;
+
+/// R1CS representation of the Merkle tree path.
+pub type BoweHopwoodPathVar = generic::BoweHopwoodPathVar<EdOnBls12_381>;
+
+pub type MerkleTreeCircuit = generic::MerkleTreeCircuit<EdOnBls12_381>;
@@ -0,0 +1,27 @@
+//! Bowe–Hopwood backend over ed-on-bls12-381, proven with BLS12-381: the curve-generic
+//! [`bowe_hopwood`](super::bowe_hopwood) backend instantiated with [`EdOnBls12_381`].
+
+pub mod common;
+pub mod constraint;
+
+pub use common::*;
+pub use constraint::*;
+
+use super::bowe_hopwood::BoweHopwoodCurve;
+
+/// The ed-on-bls12-381 curve, whose base field is the scalar field of
+/// [`Bls12_381`](ark_bls12_381::Bls12_381).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBls12_381;
+
+impl BoweHopwoodCurve for EdOnBls12_381 {
+    type Parameters = ark_ed_on_bls12_381::EdwardsParameters;
+    type Field = ark_ed_on_bls12_381::Fq;
+    type Pairing = ark_bls12_381::Bls12_381;
+
+    // 1134 bits (141 bytes): longer member records are rejected, so this must
+    // cover the 128-byte records the Pedersen backends accept
+    const LEAF_NUM_WINDOWS: usize = 6;
+    // 567 bits: two serialized field elements
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 3;
+}
@@ -0,0 +1,37 @@
+use super::EdOnBw6_761;
+use crate::commitments::bowe_hopwood::{common as generic, BoweHopwoodCurve};
+use ark_crypto_primitives::crh::TwoToOneCRH;
+use ark_crypto_primitives::CRH;
+
+/////////////////////////////
+
+pub type BoweHopwood761Field = <EdOnBw6_761 as BoweHopwoodCurve>::Field;
+
+/////////////////////////////
+
+pub type TwoToOneWindow = generic::TwoToOneWindow<EdOnBw6_761>;
+pub type TwoToOneHash = generic::TwoToOneHash<EdOnBw6_761>;
+pub type Root = generic::Root<EdOnBw6_761>;
+pub type TwoToOneHashGadget = generic::TwoToOneHashGadget<EdOnBw6_761>;
+
+pub type LeafWindow = generic::LeafWindow<EdOnBw6_761>;
+pub type LeafHash = generic::LeafHash<EdOnBw6_761>;
+pub type Leaf = generic::Leaf<EdOnBw6_761>;
+pub type LeafHashGadget = generic::LeafHashGadget<EdOnBw6_761>;
+
+pub type LeafHashParamsVar = generic::LeafHashParamsVar<EdOnBw6_761>;
+pub type TwoToOneHashParamsVar = generic::TwoToOneHashParamsVar<EdOnBw6_761>;
+
+/////////////////////////////
+
+pub type MerkleConfig = generic::MerkleConfig<EdOnBw6_761>;
+pub type MerklePath = generic::MerklePath<EdOnBw6_761>;
+pub type MembershipTree = generic::MembershipTree<EdOnBw6_761>;
+
+pub fn new_membership_tree(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as TwoToOneCRH>::Parameters,
+    leaves: &mut Vec<BoweHopwood761Field>,
+) -> MembershipTree {
+    generic::new_membership_tree::<EdOnBw6_761>(leaf_crh_params, two_to_one_crh_params, leaves)
+}
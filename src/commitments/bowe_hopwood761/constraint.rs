@@ -0,0 +1,12 @@
+use super::EdOnBw6_761;
+use crate::commitments::bowe_hopwood::constraint as generic;
+
+/// R1CS representation of the Merkle tree root.
+pub type BoweHopwoodRootVar = generic::BoweHopwoodRootVar<EdOnBw6_761>;
+
+pub type BoweHopwoodLeafVar = generic::BoweHopwoodLeafVar<EdOnBw6_761>;
+
+/// R1CS representation of the Merkle tree path.
+pub type BoweHopwoodPathVar = generic::BoweHopwoodPathVar<EdOnBw6_761>;
+
+pub type MerkleTreeCircuit = generic::MerkleTreeCircuit<EdOnBw6_761>;
@@ -0,0 +1,27 @@
+//! Bowe–Hopwood backend over ed-on-bw6-761, proven with BW6-761: the curve-generic
+//! [`bowe_hopwood`](super::bowe_hopwood) backend instantiated with [`EdOnBw6_761`].
+
+pub mod common;
+pub mod constraint;
+
+pub use common::*;
+pub use constraint::*;
+
+use super::bowe_hopwood::BoweHopwoodCurve;
+
+/// The ed-on-bw6-761 curve, whose base field is the scalar field of
+/// [`BW6_761`](ark_bw6_761::BW6_761).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBw6_761;
+
+impl BoweHopwoodCurve for EdOnBw6_761 {
+    type Parameters = ark_ed_on_bw6_761::EdwardsParameters;
+    type Field = ark_ed_on_bw6_761::Fq;
+    type Pairing = ark_bw6_761::BW6_761;
+
+    // 1134 bits (141 bytes): longer member records are rejected, so this must
+    // cover the 128-byte records the Pedersen backends accept
+    const LEAF_NUM_WINDOWS: usize = 6;
+    // 945 bits: two serialized field elements
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 5;
+}
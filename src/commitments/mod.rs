@@ -1,31 +1,119 @@
-#[cfg(all(feature = "pedersen381", feature = "pedersen761"))]
-compile_error!("Cannot enable both pedersen381 and pedersen761 features at the same time.");
-
+pub mod bowe_hopwood;
+pub mod bowe_hopwood381;
+pub mod bowe_hopwood761;
+pub mod pedersen;
+pub mod pedersen254;
+pub mod pedersen377;
 pub mod pedersen381;
 pub mod pedersen761;
+pub mod poseidon381;
+
+use ark_crypto_primitives::{
+    crh::{CRHGadget, TwoToOneCRH, TwoToOneCRHGadget},
+    merkle_tree::{constraints::PathVar, Config},
+    Path, CRH,
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, prelude::Boolean};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use blake2::{Blake2s, Digest};
+
+/// Leaf that pads membership trees to a power of two. It is hashed to the field
+/// from a fixed string rather than computed with a leaf hash, so no member
+/// record is known to hash to it and circuits that open a leaf to a record
+/// cannot be satisfied at a padding position.
+pub fn empty_leaf<F: PrimeField>() -> F {
+    F::from_le_bytes_mod_order(&Blake2s::digest(b"zkmember empty leaf"))
+}
+
+/// Hashes, gadgets and Merkle config of a membership backend whose only circuit
+/// is [`GenericMerkleTreeCircuit`], implemented by a marker type per backend,
+/// e.g. [`poseidon381::Poseidon381`].
+///
+/// A new hash/curve combination only needs a `common.rs` with those types and
+/// this impl; the Pedersen backends have their own circuits and
+/// [`pedersen::PedersenCurve`].
+pub trait MerkleBackend {
+    type Field: PrimeField;
+    type LeafHash: CRH;
+    type TwoToOneHash: TwoToOneCRH;
+    type MerkleConfig: Config<LeafHash = Self::LeafHash, TwoToOneHash = Self::TwoToOneHash> + Clone;
+    type LeafHashGadget: CRHGadget<Self::LeafHash, Self::Field>;
+    type TwoToOneHashGadget: TwoToOneCRHGadget<Self::TwoToOneHash, Self::Field>;
+}
+
+/// Proves that the public `leaf_hash` is in the tree under the public `root`,
+/// for any [`MerkleBackend`]; each such backend exports it as its
+/// `MerkleTreeCircuit`.
+pub struct GenericMerkleTreeCircuit<B: MerkleBackend> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <B::LeafHash as CRH>::Parameters,
+    pub two_to_one_crh_params: <B::TwoToOneHash as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: <B::TwoToOneHash as TwoToOneCRH>::Output,
+    pub leaf_hash: <B::LeafHash as CRH>::Output,
+
+    // This is the private witness to the circuit
+    pub authentication_path: Option<Path<B::MerkleConfig>>,
+}
+
+// Implemented by hand: deriving would require the backend's hashes to be `Clone`.
+impl<B: MerkleBackend> Clone for GenericMerkleTreeCircuit<B> {
+    fn clone(&self) -> Self {
+        Self {
+            leaf_crh_params: self.leaf_crh_params.clone(),
+            two_to_one_crh_params: self.two_to_one_crh_params.clone(),
+            root: self.root.clone(),
+            leaf_hash: self.leaf_hash.clone(),
+            authentication_path: self.authentication_path.clone(),
+        }
+    }
+}
+
+impl<B: MerkleBackend> ConstraintSynthesizer<B::Field> for GenericMerkleTreeCircuit<B> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<B::Field>) -> Result<(), SynthesisError> {
+        // Allocate parameters as constants
+        let leaf_crh_params = <B::LeafHashGadget as CRHGadget<_, _>>::ParametersVar::new_constant(
+            cs.clone(),
+            self.leaf_crh_params,
+        )?;
+        let two_to_one_crh_params =
+            <B::TwoToOneHashGadget as TwoToOneCRHGadget<_, _>>::ParametersVar::new_constant(
+                cs.clone(),
+                self.two_to_one_crh_params,
+            )?;
+
+        // Allocate public inputs
+        let root = <B::TwoToOneHashGadget as TwoToOneCRHGadget<_, _>>::OutputVar::new_input(
+            ark_relations::ns!(cs, "root_var"),
+            || Ok(&self.root),
+        )?;
+
+        let hashed_leaf = <B::LeafHashGadget as CRHGadget<_, _>>::OutputVar::new_input(
+            ark_relations::ns!(cs, "leaf_var"),
+            || Ok(&self.leaf_hash),
+        )?;
+
+        // Allocate path as witness
+        let path = PathVar::<B::MerkleConfig, B::LeafHashGadget, B::TwoToOneHashGadget, B::Field>::new_witness(
+            ark_relations::ns!(cs, "path_witness"),
+            || {
+                self.authentication_path
+                    .as_ref()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+
+        let is_member: Boolean<B::Field> = path.verify_membership(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            &hashed_leaf,
+        )?;
+
+        is_member.enforce_equal(&Boolean::TRUE)?;
 
-#[cfg(feature = "generic")]
-use ark_crypto_primitives::{crh::TwoToOneCRH, merkle_tree::Config, Path, CRH};
-#[cfg(feature = "generic")]
-use ark_ff::Field as FiniteField;
-
-#[cfg(feature = "generic")]
-#[derive(Clone)]
-pub struct GenericMerkleTreeCircuit<Field, LeafHash, TwoToOneHash, MerkleConfig>
-where
-    Field: FiniteField,
-    LeafHash: ark_crypto_primitives::crh::CRH,
-    TwoToOneHash: ark_crypto_primitives::crh::TwoToOneCRH,
-    MerkleConfig: Config,
-{
-    // public constants
-    pub leaf_crh_params: <LeafHash as CRH>::Parameters,
-    pub two_to_one_crh_params: <TwoToOneHash as TwoToOneCRH>::Parameters,
-
-    // public inputs
-    pub root: <TwoToOneHash as TwoToOneCRH>::Output,
-    pub leaf_hash: Field,
-
-    // private witness
-    pub authentication_path: Option<Path<MerkleConfig>>,
+        Ok(())
+    }
 }
@@ -0,0 +1,651 @@
+use super::{PedersenCurve, WINDOW_SIZE};
+use crate::member::Identity;
+use ark_crypto_primitives::crh::constraints::{CRHGadget, TwoToOneCRHGadget};
+use ark_crypto_primitives::crh::injective_map::constraints::{
+    PedersenCRHCompressorGadget, TECompressorGadget,
+};
+use ark_crypto_primitives::crh::TwoToOneCRH;
+use ark_crypto_primitives::crh::{
+    injective_map::{PedersenCRHCompressor, TECompressor},
+    pedersen,
+};
+use ark_crypto_primitives::merkle_tree::Config;
+use ark_crypto_primitives::{MerkleTree, Path, CRH};
+use ark_ec::twisted_edwards_extended::GroupProjective;
+use ark_ff::{One, PrimeField, ToBytes, Zero};
+use ark_r1cs_std::{fields::fp::FpVar, groups::curves::twisted_edwards::AffineVar};
+use ark_std::io::{Result as IoResult, Write};
+use blake2::{Blake2s, Digest};
+use core::marker::PhantomData;
+
+/////////////////////////////
+
+/// Projective form of the embedded Edwards curve of `C`.
+pub type EdwardsProjective<C> = GroupProjective<<C as PedersenCurve>::Parameters>;
+
+/// R1CS representation of a point of the embedded Edwards curve of `C`.
+pub type EdwardsVar<C> =
+    AffineVar<<C as PedersenCurve>::Parameters, FpVar<<C as PedersenCurve>::Field>>;
+
+/////////////////////////////
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TwoToOneWindow<C>(PhantomData<C>);
+impl<C: PedersenCurve> pedersen::Window for TwoToOneWindow<C> {
+    const WINDOW_SIZE: usize = WINDOW_SIZE;
+    const NUM_WINDOWS: usize = C::TWO_TO_ONE_NUM_WINDOWS;
+}
+
+pub type TwoToOneHash<C> =
+    PedersenCRHCompressor<EdwardsProjective<C>, TECompressor, TwoToOneWindow<C>>;
+pub type Root<C> = <TwoToOneHash<C> as TwoToOneCRH>::Output;
+
+pub type TwoToOneHashGadget<C> = PedersenCRHCompressorGadget<
+    EdwardsProjective<C>,
+    TECompressor,
+    TwoToOneWindow<C>,
+    EdwardsVar<C>,
+    TECompressorGadget,
+>;
+
+/////////////////////////////
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct LeafWindow<C>(PhantomData<C>);
+impl<C: PedersenCurve> pedersen::Window for LeafWindow<C> {
+    const WINDOW_SIZE: usize = WINDOW_SIZE;
+    const NUM_WINDOWS: usize = C::LEAF_NUM_WINDOWS;
+}
+
+pub type LeafHash<C> = PedersenCRHCompressor<EdwardsProjective<C>, TECompressor, LeafWindow<C>>;
+pub type Leaf<C> = <LeafHash<C> as CRH>::Output;
+
+pub type LeafHashGadget<C> = PedersenCRHCompressorGadget<
+    EdwardsProjective<C>,
+    TECompressor,
+    LeafWindow<C>,
+    EdwardsVar<C>,
+    TECompressorGadget,
+>;
+
+/////////////////////////////
+
+pub type LeafHashParamsVar<C> =
+    <LeafHashGadget<C> as CRHGadget<LeafHash<C>, <C as PedersenCurve>::Field>>::ParametersVar;
+
+pub type TwoToOneHashParamsVar<C> = <TwoToOneHashGadget<C> as TwoToOneCRHGadget<
+    TwoToOneHash<C>,
+    <C as PedersenCurve>::Field,
+>>::ParametersVar;
+
+/////////////////////////////
+
+#[derive(Clone)]
+pub struct MerkleConfig<C>(PhantomData<C>);
+impl<C: PedersenCurve> Config for MerkleConfig<C> {
+    type LeafHash = LeafHash<C>;
+    type TwoToOneHash = TwoToOneHash<C>;
+}
+
+pub type MerklePath<C> = Path<MerkleConfig<C>>;
+
+pub type MembershipTree<C> = MerkleTree<MerkleConfig<C>>;
+
+pub fn new_membership_tree<C: PedersenCurve>(
+    leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash<C> as CRH>::Parameters,
+    leaves: &mut Vec<C::Field>,
+) -> MembershipTree<C> {
+    clean_membership_list::<C>(leaves);
+    MembershipTree::<C>::new(leaf_crh_params, two_to_one_crh_params, leaves.as_ref()).unwrap()
+}
+
+/// Configures [`FixedDepthTree`]s of depth `D`, i.e. with room for `2^D`
+/// members whatever their actual count.
+///
+/// Missing leaves are [`empty_leaf`], so a tree is the [`MembershipTree`] over
+/// its leaves padded with it to `2^D`. The roots of empty subtrees of every
+/// height are computed once here, and trees only hash the occupied part.
+#[derive(Clone)]
+pub struct MembershipTreeBuilder<C: PedersenCurve> {
+    leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    /// `empty_subtrees[k]` is the root of an empty subtree of height `k`, so
+    /// `empty_subtrees[0]` is the node of a padding leaf.
+    empty_subtrees: Vec<C::Field>,
+}
+
+impl<C: PedersenCurve> MembershipTreeBuilder<C> {
+    pub fn new(
+        leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+        depth: usize,
+    ) -> Self {
+        assert!(
+            (1..usize::BITS as usize).contains(&depth),
+            "a membership tree has at least two leaves"
+        );
+
+        let mut empty_subtrees = vec![hash_leaf::<C>(&leaf_crh_params, &empty_leaf::<C>())];
+        for level in 1..=depth {
+            let below = empty_subtrees[level - 1];
+            empty_subtrees.push(hash_children::<C>(&two_to_one_crh_params, &below, &below));
+        }
+
+        Self {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            empty_subtrees,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.empty_subtrees.len() - 1
+    }
+
+    /// Maximum number of members, `2^D`.
+    pub fn capacity(&self) -> usize {
+        1 << self.depth()
+    }
+
+    /// Builds a tree over `leaves`, or returns `None` if they exceed the
+    /// capacity.
+    pub fn build(&self, leaves: &[C::Field]) -> Option<FixedDepthTree<C>> {
+        if leaves.len() > self.capacity() {
+            return None;
+        }
+
+        let mut levels = vec![leaves
+            .iter()
+            .map(|leaf| hash_leaf::<C>(&self.leaf_crh_params, leaf))
+            .collect::<Vec<_>>()];
+        for level in 1..=self.depth() {
+            let below = &levels[level - 1];
+            let nodes = below
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&self.empty_subtrees[level - 1]);
+                    hash_children::<C>(&self.two_to_one_crh_params, &pair[0], right)
+                })
+                .collect();
+            levels.push(nodes);
+        }
+
+        Some(FixedDepthTree {
+            builder: self.clone(),
+            leaves: leaves.to_vec(),
+            levels,
+        })
+    }
+}
+
+/// Membership tree of a fixed depth built by a [`MembershipTreeBuilder`].
+///
+/// Its paths always have the configured depth, so one [`MerkleTreeCircuit`]
+/// (and one proving key) serves the tree at every member count.
+///
+/// Only the Pedersen backends have fixed-depth trees: the Poseidon and
+/// Bowe-Hopwood backends still pad with `new_membership_tree`, so their keys
+/// only serve member counts up to the same power of two.
+///
+/// [`MerkleTreeCircuit`]: super::MerkleTreeCircuit
+#[derive(Clone)]
+pub struct FixedDepthTree<C: PedersenCurve> {
+    builder: MembershipTreeBuilder<C>,
+    leaves: Vec<C::Field>,
+    /// Occupied nodes by level, from the leaf nodes up to the root. Nodes past
+    /// the end of a level are roots of empty subtrees.
+    levels: Vec<Vec<C::Field>>,
+}
+
+impl<C: PedersenCurve> FixedDepthTree<C> {
+    pub fn depth(&self) -> usize {
+        self.builder.depth()
+    }
+
+    /// Number of members, not counting padding.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn leaf(&self, index: usize) -> Option<C::Field> {
+        self.leaves.get(index).copied()
+    }
+
+    pub fn leaf_crh_params(&self) -> &<LeafHash<C> as CRH>::Parameters {
+        &self.builder.leaf_crh_params
+    }
+
+    pub fn two_to_one_crh_params(&self) -> &<TwoToOneHash<C> as TwoToOneCRH>::Parameters {
+        &self.builder.two_to_one_crh_params
+    }
+
+    pub fn root(&self) -> Root<C> {
+        self.node(self.depth(), 0)
+    }
+
+    /// Appends `leaf`, rehashing only its path, and returns its index, or
+    /// returns `None` if the tree is full.
+    pub fn push(&mut self, leaf: C::Field) -> Option<usize> {
+        let index = self.len();
+        if index == self.builder.capacity() {
+            return None;
+        }
+
+        let mut node = hash_leaf::<C>(&self.builder.leaf_crh_params, &leaf);
+        self.leaves.push(leaf);
+        self.levels[0].push(node);
+        for level in 1..=self.depth() {
+            let position = index >> level;
+            let left = self.node(level - 1, 2 * position);
+            let right = self.node(level - 1, 2 * position + 1);
+            node = hash_children::<C>(&self.builder.two_to_one_crh_params, &left, &right);
+
+            let nodes = &mut self.levels[level];
+            if position < nodes.len() {
+                nodes[position] = node;
+            } else {
+                nodes.push(node);
+            }
+        }
+        Some(index)
+    }
+
+    /// Path of the member at `index`, or `None` if there is none.
+    pub fn generate_proof(&self, index: usize) -> Option<MerklePath<C>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(MerklePath::<C> {
+            leaf_sibling_hash: self.node(0, index ^ 1),
+            // Ordered from the root down, like `MerkleTree::generate_proof`
+            auth_path: (1..self.depth())
+                .rev()
+                .map(|level| self.node(level, (index >> level) ^ 1))
+                .collect(),
+            leaf_index: index,
+        })
+    }
+
+    fn node(&self, level: usize, position: usize) -> C::Field {
+        self.levels[level]
+            .get(position)
+            .copied()
+            .unwrap_or(self.builder.empty_subtrees[level])
+    }
+}
+
+/// Leaf that pads a [`FixedDepthTree`] or a [`MembershipTree`]; see
+/// [`crate::commitments::empty_leaf`]. Zero would not do: an all-zero record
+/// hashes to zero.
+pub fn empty_leaf<C: PedersenCurve>() -> C::Field {
+    crate::commitments::empty_leaf::<C::Field>()
+}
+
+/// Node of `leaf` in a [`MerkleTree`], as computed by `MerkleTree::new`.
+fn hash_leaf<C: PedersenCurve>(
+    leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+    leaf: &C::Field,
+) -> C::Field {
+    <LeafHash<C> as CRH>::evaluate(leaf_crh_params, &ark_ff::to_bytes![leaf].unwrap()).unwrap()
+}
+
+/// Parent of two nodes in a [`MerkleTree`], as computed by `MerkleTree::new`.
+fn hash_children<C: PedersenCurve>(
+    two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    left: &C::Field,
+    right: &C::Field,
+) -> C::Field {
+    <TwoToOneHash<C> as TwoToOneCRH>::evaluate(
+        two_to_one_crh_params,
+        &ark_ff::to_bytes![left].unwrap(),
+        &ark_ff::to_bytes![right].unwrap(),
+    )
+    .unwrap()
+}
+
+/// Leaf of a [`RevocationTree`]: a revoked value and the next larger revoked
+/// value, or zero if there is none. The leaf proves that nothing in between is
+/// revoked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RevocationLeaf<C: PedersenCurve> {
+    pub value: C::Field,
+    pub next_value: C::Field,
+}
+
+impl<C: PedersenCurve> RevocationLeaf<C> {
+    /// Whether `value` falls strictly inside the gap covered by this leaf.
+    pub fn covers(&self, value: &C::Field) -> bool {
+        self.value < *value && (*value < self.next_value || self.next_value.is_zero())
+    }
+}
+
+impl<C: PedersenCurve> ToBytes for RevocationLeaf<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.value.write(&mut writer)?;
+        self.next_value.write(&mut writer)
+    }
+}
+
+/// Indexed Merkle tree over a sorted list of revoked values (e.g. the leaves of
+/// banned members), supporting proofs that a value is *not* revoked.
+///
+/// Zero acts as the smallest element and cannot be revoked. Padding leaves are
+/// `(0, 1)`, an empty gap.
+pub struct RevocationTree<C: PedersenCurve> {
+    leaves: Vec<RevocationLeaf<C>>,
+    tree: MembershipTree<C>,
+}
+
+impl<C: PedersenCurve> RevocationTree<C> {
+    pub fn new(
+        leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+        revoked: &[C::Field],
+    ) -> Self {
+        let mut values = revoked
+            .iter()
+            .copied()
+            .filter(|value| !value.is_zero())
+            .collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+
+        let mut leaves = vec![RevocationLeaf::<C>::default()];
+        for value in values {
+            leaves.last_mut().unwrap().next_value = value;
+            leaves.push(RevocationLeaf {
+                value,
+                next_value: C::Field::zero(),
+            });
+        }
+
+        let padding = RevocationLeaf {
+            value: C::Field::zero(),
+            next_value: C::Field::one(),
+        };
+        leaves.resize(leaves.len().next_power_of_two().max(2), padding);
+
+        let tree =
+            MembershipTree::<C>::new(leaf_crh_params, two_to_one_crh_params, &leaves).unwrap();
+        Self { leaves, tree }
+    }
+
+    pub fn root(&self) -> Root<C> {
+        self.tree.root()
+    }
+
+    pub fn contains(&self, value: &C::Field) -> bool {
+        !value.is_zero() && self.leaves.iter().any(|leaf| leaf.value == *value)
+    }
+
+    /// Returns the leaf whose gap covers `value` and its path, or `None` if
+    /// `value` is revoked.
+    pub fn prove_non_membership(
+        &self,
+        value: &C::Field,
+    ) -> Option<(RevocationLeaf<C>, MerklePath<C>)> {
+        let index = self.leaves.iter().position(|leaf| leaf.covers(value))?;
+        Some((self.leaves[index], self.tree.generate_proof(index).unwrap()))
+    }
+}
+
+/// Fixed-size window of the most recent roots of a [`MembershipTree`]. Its
+/// commitment is the root of a small tree over the window, so a proof against
+/// any recent root stays valid while new members register.
+#[derive(Clone)]
+pub struct RootHistory<C: PedersenCurve> {
+    roots: Vec<Root<C>>,
+    oldest: usize,
+}
+
+impl<C: PedersenCurve> RootHistory<C> {
+    /// Creates a window of `size` roots, a power of two, filled with `root`.
+    pub fn new(size: usize, root: Root<C>) -> Self {
+        assert!(
+            size >= 2 && size.is_power_of_two(),
+            "root history size must be a power of two"
+        );
+        Self {
+            roots: vec![root; size],
+            oldest: 0,
+        }
+    }
+
+    /// Records `root` as the latest root, evicting the oldest one.
+    pub fn push(&mut self, root: Root<C>) {
+        self.roots[self.oldest] = root;
+        self.oldest = (self.oldest + 1) % self.roots.len();
+    }
+
+    pub fn contains(&self, root: &Root<C>) -> bool {
+        self.roots.contains(root)
+    }
+
+    pub fn tree(
+        &self,
+        leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    ) -> MembershipTree<C> {
+        MembershipTree::<C>::new(leaf_crh_params, two_to_one_crh_params, &self.roots).unwrap()
+    }
+
+    /// The public input of circuits proving membership under a recent root.
+    pub fn commitment(
+        &self,
+        leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    ) -> Root<C> {
+        self.tree(leaf_crh_params, two_to_one_crh_params).root()
+    }
+
+    /// Path of `root` in the history tree, or `None` if it is not recent.
+    pub fn generate_proof(
+        &self,
+        leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+        root: &Root<C>,
+    ) -> Option<MerklePath<C>> {
+        let index = self.roots.iter().position(|recent| recent == root)?;
+        Some(
+            self.tree(leaf_crh_params, two_to_one_crh_params)
+                .generate_proof(index)
+                .unwrap(),
+        )
+    }
+}
+
+/// Derives the nullifier of `identity` for `external_nullifier` as
+/// `Blake2s(secret || external_nullifier)`, the construction of the arkworks
+/// Blake2s PRF, reduced into the field.
+///
+/// Unlike the Pedersen hashes, Blake2s is not linear: the nullifiers of one
+/// identity under two external nullifiers differ by a shift that depends on the
+/// secret, so they cannot be linked without it.
+pub fn member_nullifier<C: PedersenCurve>(
+    identity: &Identity,
+    external_nullifier: &C::Field,
+) -> C::Field {
+    let mut input = identity.secret().to_vec();
+    input.extend(ark_ff::to_bytes![external_nullifier].unwrap());
+
+    C::Field::from_le_bytes_mod_order(&Blake2s::digest(&input))
+}
+
+/// Pads `leaves` to a power of two, and to at least two, with [`empty_leaf`].
+pub(crate) fn clean_membership_list<C: PedersenCurve>(leaves: &mut Vec<C::Field>) {
+    let num_members = leaves.len();
+
+    let num_needed = if num_members == 1 {
+        1
+    } else {
+        num_members.next_power_of_two() - num_members
+    };
+
+    leaves.append(&mut vec![empty_leaf::<C>(); num_needed]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::member::{Member, MemberError};
+
+    fn setup<C: PedersenCurve>() -> (
+        <LeafHash<C> as CRH>::Parameters,
+        <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    ) {
+        let mut rng = ark_std::test_rng();
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+        (leaf_crh_params, two_to_one_crh_params)
+    }
+
+    fn check_fixed_depth_tree<C: PedersenCurve>() {
+        let (leaf_crh_params, two_to_one_crh_params) = setup::<C>();
+        let builder = MembershipTreeBuilder::<C>::new(
+            leaf_crh_params.clone(),
+            two_to_one_crh_params.clone(),
+            3,
+        );
+
+        let leaves = [1u64, 2, 3, 4, 5, 6, 7, 8, 9]
+            .map(|i| Member::new(i.to_string(), format!("{}@usc.edu", i), None))
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        assert!(builder.build(&leaves).is_none());
+
+        let mut pushed = builder.build(&[]).unwrap();
+        for count in 1..=8 {
+            assert_eq!(pushed.push(leaves[count - 1]), Some(count - 1));
+            let tree = builder.build(&leaves[..count]).unwrap();
+            assert_eq!(pushed.root(), tree.root());
+
+            // Same tree as the leaves padded to the capacity
+            let mut padded_leaves = leaves[..count].to_vec();
+            padded_leaves.resize(8, empty_leaf::<C>());
+            let padded =
+                MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &padded_leaves)
+                    .unwrap();
+            assert_eq!(tree.root(), padded.root());
+
+            for (index, leaf) in leaves[..count].iter().enumerate() {
+                let path = tree.generate_proof(index).unwrap();
+                assert_eq!(path.auth_path.len(), 2);
+                assert!(path
+                    .verify(&leaf_crh_params, &two_to_one_crh_params, &tree.root(), leaf)
+                    .unwrap());
+                let padded_path = padded.generate_proof(index).unwrap();
+                assert_eq!(path.leaf_sibling_hash, padded_path.leaf_sibling_hash);
+                assert_eq!(path.auth_path, padded_path.auth_path);
+            }
+            assert!(tree.generate_proof(count).is_none());
+        }
+        assert_eq!(pushed.push(leaves[8]), None);
+    }
+
+    fn check_member_record_length<C: PedersenCurve>() {
+        let (leaf_crh_params, _) = setup::<C>();
+
+        let member = Member::new(
+            "jonathan".into(),
+            "jonathan.smith@cs.stanford.edu".into(),
+            None,
+        );
+        assert!(member.hash::<LeafHash<C>>(&leaf_crh_params).is_ok());
+
+        // Longer records are rejected instead of panicking inside the hash
+        let member = Member::new(
+            "jonathan".into(),
+            format!("{}@cs.stanford.edu", "jonathan.smith".repeat(8)),
+            None,
+        );
+        assert!(matches!(
+            member.hash::<LeafHash<C>>(&leaf_crh_params),
+            Err(MemberError::RecordTooLong { max, .. }) if max == C::MEMBER_RECORD_BYTES
+        ));
+    }
+
+    fn check_revocation_tree<C: PedersenCurve>() {
+        let (leaf_crh_params, two_to_one_crh_params) = setup::<C>();
+
+        let leaves = [1u64, 2, 3, 4, 5]
+            .map(|i| Member::new(i.to_string(), format!("{}@usc.edu", i), None))
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let revoked = [leaves[1], leaves[3]];
+        let tree = RevocationTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &revoked);
+
+        for leaf in leaves {
+            let is_revoked = revoked.contains(&leaf);
+            assert_eq!(tree.contains(&leaf), is_revoked);
+
+            match tree.prove_non_membership(&leaf) {
+                Some((low_leaf, path)) => {
+                    assert!(!is_revoked);
+                    assert!(low_leaf.covers(&leaf));
+                    assert!(path
+                        .verify(
+                            &leaf_crh_params,
+                            &two_to_one_crh_params,
+                            &tree.root(),
+                            &low_leaf
+                        )
+                        .unwrap());
+                }
+                None => assert!(is_revoked),
+            }
+        }
+    }
+
+    fn check_root_history<C: PedersenCurve>() {
+        let (leaf_crh_params, two_to_one_crh_params) = setup::<C>();
+
+        let roots = [1u64, 2, 3, 4, 5, 6].map(C::Field::from);
+        let mut history = RootHistory::<C>::new(4, roots[0]);
+        for root in &roots[1..] {
+            history.push(*root);
+        }
+
+        let commitment = history.commitment(&leaf_crh_params, &two_to_one_crh_params);
+        for (i, root) in roots.iter().enumerate() {
+            // Only the last four roots are kept
+            assert_eq!(history.contains(root), i >= 2);
+
+            match history.generate_proof(&leaf_crh_params, &two_to_one_crh_params, root) {
+                Some(path) => assert!(path
+                    .verify(&leaf_crh_params, &two_to_one_crh_params, &commitment, root)
+                    .unwrap()),
+                None => assert!(i < 2),
+            }
+        }
+    }
+
+    fn check_nullifier_unlinkable<C: PedersenCurve>() {
+        let identities = [Identity::random(), Identity::random()];
+        let polls = [C::Field::from(7u64), C::Field::from(8u64)];
+        let shift = |identity: &Identity| {
+            member_nullifier::<C>(identity, &polls[1]) - member_nullifier::<C>(identity, &polls[0])
+        };
+
+        // Nullifiers are bound to the context they were derived for
+        assert_ne!(
+            member_nullifier::<C>(&identities[0], &polls[0]),
+            member_nullifier::<C>(&identities[0], &polls[1])
+        );
+
+        // Moving to another context shifts every identity's nullifier differently,
+        // so no public shift links one identity's nullifiers across contexts
+        assert_ne!(shift(&identities[0]), shift(&identities[1]));
+    }
+
+    all_curves_tests! {
+        fixed_depth_tree => check_fixed_depth_tree,
+        member_record_length => check_member_record_length,
+        revocation_tree => check_revocation_tree,
+        root_history => check_root_history,
+        nullifier_unlinkable => check_nullifier_unlinkable,
+    }
+}
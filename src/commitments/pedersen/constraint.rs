@@ -0,0 +1,1740 @@
+use super::common::{
+    FixedDepthTree, LeafHash, LeafHashGadget, LeafHashParamsVar, MerkleConfig, MerklePath,
+    RevocationLeaf, Root, TwoToOneHash, TwoToOneHashGadget, TwoToOneHashParamsVar,
+};
+use super::PedersenCurve;
+use crate::gadgets::compare::is_less_than;
+use crate::gadgets::domain::{byte_to_fp_var, domain_to_field, enforce_email_domain};
+use crate::gadgets::select::select_private;
+use crate::gadgets::timestamp::{
+    conditional_enforce_before, duration_to_field, enforce_elapsed_at_least, enforce_not_after,
+    timestamp_from_be_bytes, timestamp_to_field,
+};
+use crate::member::{
+    Identity, Member, EMAIL_LEN_OFFSET, END_DATE_FLAG_OFFSET, END_DATE_OFFSET,
+    IDENTITY_COMMITMENT_BYTES, IDENTITY_COMMITMENT_FLAG_OFFSET, IDENTITY_COMMITMENT_OFFSET,
+    ID_LEN_OFFSET, ID_OFFSET, JOIN_DATE_OFFSET, MEMBER_SECRET_BYTES, TIMESTAMP_BYTES,
+};
+use ark_crypto_primitives::{
+    crh::{CRHGadget, TwoToOneCRH, TwoToOneCRHGadget},
+    prf::blake2s::constraints::evaluate_blake2s,
+    PathVar, CRH,
+};
+use ark_ff::{Field, One, Zero};
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::{
+    eq::EqGadget, fields::fp::FpVar, fields::FieldVar, prelude::Boolean, uint32::UInt32,
+    uint8::UInt8, ToBitsGadget, ToBytesGadget,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use chrono::{DateTime, Duration, Utc};
+
+/// R1CS representation of the Merkle tree root.
+pub type PedersenRootVar<C> = <TwoToOneHashGadget<C> as TwoToOneCRHGadget<
+    TwoToOneHash<C>,
+    <C as PedersenCurve>::Field,
+>>::OutputVar;
+
+pub type PedersenLeafVar<C> =
+    <LeafHashGadget<C> as CRHGadget<LeafHash<C>, <C as PedersenCurve>::Field>>::OutputVar;
+
+/// R1CS representation of the Merkle tree path.
+pub type PedersenPathVar<C> =
+    PathVar<MerkleConfig<C>, LeafHashGadget<C>, TwoToOneHashGadget<C>, <C as PedersenCurve>::Field>;
+
+/// Serialized member record as private witness bytes.
+type MemberRecordVar<C> = Vec<UInt8<<C as PedersenCurve>::Field>>;
+
+#[derive(Clone)]
+pub struct MerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub leaf_hash: C::Field,
+
+    // This is the private witness to the circuit
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> MerkleTreeCircuit<C> {
+    /// Circuit for a tree of `depth` with an all-zero statement and witness. The
+    /// circuit's shape depends only on the depth, so it stands in for every
+    /// member's circuit when sizing or setting up keys.
+    pub fn placeholder(
+        leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+        depth: usize,
+    ) -> Self {
+        assert!(depth >= 1, "a membership tree has at least two leaves");
+
+        Self {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: C::Field::zero(),
+            leaf_hash: C::Field::zero(),
+            authentication_path: Some(MerklePath::<C> {
+                leaf_sibling_hash: C::Field::zero(),
+                auth_path: vec![C::Field::zero(); depth - 1],
+                leaf_index: 0,
+            }),
+        }
+    }
+
+    /// Circuit proving that the member at `index` of `tree` is in it, or `None`
+    /// if there is no such member. Its shape is that of the [`placeholder`] of
+    /// `tree.depth()`, whatever the member count.
+    ///
+    /// [`placeholder`]: Self::placeholder
+    pub fn for_member(tree: &FixedDepthTree<C>, index: usize) -> Option<Self> {
+        let authentication_path = tree.generate_proof(index)?;
+        Some(Self {
+            leaf_crh_params: tree.leaf_crh_params().clone(),
+            two_to_one_crh_params: tree.two_to_one_crh_params().clone(),
+            root: tree.root(),
+            leaf_hash: tree.leaf(index)?,
+            authentication_path: Some(authentication_path),
+        })
+    }
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for MerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+
+        let hashed_leaf: PedersenLeafVar<C> =
+            PedersenLeafVar::<C>::new_input(ark_relations::ns!(cs, "leaf_var"), || {
+                Ok(&self.leaf_hash)
+            })?;
+
+        // Allocate path as witness
+        let path: PedersenPathVar<C> =
+            PedersenPathVar::<C>::new_witness(ark_relations::ns!(cs, "path_witness"), || {
+                self.authentication_path
+                    .as_ref()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })?;
+
+        let is_member: Boolean<C::Field> = path.verify_membership(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            &hashed_leaf,
+        )?;
+
+        is_member.enforce_equal(&Boolean::TRUE)?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit that keeps the member's leaf private.
+///
+/// Unlike [`MerkleTreeCircuit`], the only public input is the root, so every
+/// member of a tree produces proofs against the same statement. The member
+/// record is a private witness and, like in [`ContextMerkleTreeCircuit`], the
+/// prover opens its identity commitment: knowing the tree is not enough to
+/// prove membership, only the [`Identity`] behind a record is.
+#[derive(Clone)]
+pub struct AnonymousMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // This is the public input to the circuit
+    pub root: Root<C>,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub identity: Option<Identity>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for AnonymousMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        let _ =
+            enforce_identity_opening::<C>(cs, &leaf_crh_params, &record, self.identity.as_ref())?;
+
+        Ok(())
+    }
+}
+
+/// Proves membership in one of several trees, e.g. one per organization,
+/// without revealing which: the member record, its path and the index of the
+/// selected root are private, and the prover opens the record's identity
+/// commitment as in [`AnonymousMerkleTreeCircuit`]. The number of roots is
+/// fixed at setup time.
+#[derive(Clone)]
+pub struct MultiRootMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub roots: Vec<Root<C>>,
+
+    // These are the private witnesses to the circuit
+    pub selector: Option<usize>,
+    pub member: Option<Member>,
+    pub identity: Option<Identity>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for MultiRootMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let roots = self
+            .roots
+            .iter()
+            .map(|root| {
+                PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(root))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = select_private(cs.clone(), &roots, self.selector)?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        let _ =
+            enforce_identity_opening::<C>(cs, &leaf_crh_params, &record, self.identity.as_ref())?;
+
+        Ok(())
+    }
+}
+
+/// Anonymous membership under any root recorded in a [`RootHistory`]: the
+/// member's root is private and proven to be in the history tree, whose root is
+/// the only public input. The member record under that root is private too, and
+/// the prover opens its identity commitment as in [`AnonymousMerkleTreeCircuit`].
+///
+/// [`RootHistory`]: super::common::RootHistory
+#[derive(Clone)]
+pub struct HistoryMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // This is the public input to the circuit
+    pub history_root: Root<C>,
+
+    // These are the private witnesses to the circuit
+    pub root: Option<Root<C>>,
+    pub history_path: Option<MerklePath<C>>,
+    pub member: Option<Member>,
+    pub identity: Option<Identity>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for HistoryMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let history_root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "history_root"), || {
+                Ok(&self.history_root)
+            })?;
+
+        // The member's root must be a recent one
+        let root = PedersenRootVar::<C>::new_witness(ark_relations::ns!(cs, "root_var"), || {
+            self.root.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let history_path: PedersenPathVar<C> =
+            PedersenPathVar::<C>::new_witness(ark_relations::ns!(cs, "history_path"), || {
+                self.history_path
+                    .as_ref()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })?;
+        history_path
+            .verify_membership(
+                &leaf_crh_params,
+                &two_to_one_crh_params,
+                &history_root,
+                &root,
+            )?
+            .enforce_equal(&Boolean::TRUE)?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        let _ =
+            enforce_identity_opening::<C>(cs, &leaf_crh_params, &record, self.identity.as_ref())?;
+
+        Ok(())
+    }
+}
+
+/// Proves that `k` distinct private member records are all in the tree under one
+/// public root, e.g. that a quorum of officers signed off. Each record's
+/// identity commitment is opened as in [`AnonymousMerkleTreeCircuit`], so only
+/// the officers themselves can take part. `k` is the number of paths, fixed at
+/// setup time; the CRH parameters are allocated once and shared by every path.
+#[derive(Clone)]
+pub struct BatchMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // This is the public input to the circuit
+    pub root: Root<C>,
+
+    // These are the private witnesses to the circuit
+    pub members: Vec<Option<Member>>,
+    pub identities: Vec<Option<Identity>>,
+    pub authentication_paths: Vec<Option<MerklePath<C>>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for BatchMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        if self.members.len() != self.authentication_paths.len()
+            || self.identities.len() != self.authentication_paths.len()
+        {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+
+        let mut hashed_leaves: Vec<PedersenLeafVar<C>> = Vec::with_capacity(self.members.len());
+        for ((member, identity), authentication_path) in self
+            .members
+            .iter()
+            .zip(&self.identities)
+            .zip(&self.authentication_paths)
+        {
+            // Recompute the leaf from the private member record and check its path
+            let (record, hashed_leaf) = enforce_member_in_tree::<C>(
+                cs.clone(),
+                &leaf_crh_params,
+                &two_to_one_crh_params,
+                &root,
+                member.as_ref(),
+                authentication_path.as_ref(),
+            )?;
+
+            let _ = enforce_identity_opening::<C>(
+                cs.clone(),
+                &leaf_crh_params,
+                &record,
+                identity.as_ref(),
+            )?;
+
+            // The same member must not be counted twice
+            for other in &hashed_leaves {
+                hashed_leaf.enforce_not_equal(other)?;
+            }
+            hashed_leaves.push(hashed_leaf);
+        }
+
+        Ok(())
+    }
+}
+
+/// Membership circuit that hashes the member record inside the circuit.
+///
+/// The serialized [`Member`] is a private witness, so a proof attests knowledge
+/// of a full member record whose leaf is in the tree, not just of some leaf.
+#[derive(Clone)]
+pub struct MemberMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // This is the public input to the circuit
+    pub root: Root<C>,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for MemberMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+
+        // Recompute the leaf from the private member record and check its path
+        let _ = enforce_member_in_tree::<C>(
+            cs,
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit that also proves the membership is active at the public
+/// time `now`: the member joined at or before `now` and, if the record has an end
+/// date, `now` is before it.
+#[derive(Clone)]
+pub struct ExpiryMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub now: DateTime<Utc>,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for ExpiryMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let now = FpVar::new_input(ark_relations::ns!(cs, "now"), || {
+            Ok(timestamp_to_field::<C::Field>(&self.now))
+        })?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs,
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        // Decode the timestamps from their fixed offsets in the record
+        let join_date =
+            timestamp_from_be_bytes(&record[JOIN_DATE_OFFSET..JOIN_DATE_OFFSET + TIMESTAMP_BYTES])?;
+        let end_date =
+            timestamp_from_be_bytes(&record[END_DATE_OFFSET..END_DATE_OFFSET + TIMESTAMP_BYTES])?;
+
+        let end_date_flag = record[END_DATE_FLAG_OFFSET].to_bits_le()?;
+        for bit in &end_date_flag[1..] {
+            bit.enforce_equal(&Boolean::FALSE)?;
+        }
+
+        enforce_not_after(&join_date, &now)?;
+        conditional_enforce_before(&now, &end_date, &end_date_flag[0])?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit proving the member joined at least `min_seniority` before
+/// the public time `now`, without disclosing the join date.
+#[derive(Clone)]
+pub struct SeniorityMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub now: DateTime<Utc>,
+    pub min_seniority: Duration,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for SeniorityMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let now = FpVar::new_input(ark_relations::ns!(cs, "now"), || {
+            Ok(timestamp_to_field::<C::Field>(&self.now))
+        })?;
+        let min_seniority = FpVar::new_input(ark_relations::ns!(cs, "min_seniority"), || {
+            Ok(duration_to_field::<C::Field>(&self.min_seniority))
+        })?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs,
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        let join_date =
+            timestamp_from_be_bytes(&record[JOIN_DATE_OFFSET..JOIN_DATE_OFFSET + TIMESTAMP_BYTES])?;
+        enforce_elapsed_at_least(&join_date, &now, &min_seniority)?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit that discloses only the domain of the member's email: it
+/// proves the private record in the tree has an email ending in `@domain`.
+#[derive(Clone)]
+pub struct EmailDomainMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub domain: String,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for EmailDomainMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let domain = FpVar::new_input(ark_relations::ns!(cs, "domain"), || {
+            Ok(domain_to_field::<C::Field>(&self.domain))
+        })?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs,
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        // The email directly follows the id
+        let id_len = byte_to_fp_var(&record[ID_LEN_OFFSET])?;
+        let email_len = byte_to_fp_var(&record[EMAIL_LEN_OFFSET])?;
+        let email_start = id_len + FpVar::constant(C::Field::from(ID_OFFSET as u64));
+
+        enforce_email_domain(&record, &email_start, &email_len, &domain)?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit proving the member is in the allow tree under `root` AND
+/// its leaf is not revoked in the [`RevocationTree`] under `revocation_root`.
+///
+/// [`RevocationTree`]: super::common::RevocationTree
+#[derive(Clone)]
+pub struct RevocationMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub revocation_root: Root<C>,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub authentication_path: Option<MerklePath<C>>,
+    pub low_leaf: Option<RevocationLeaf<C>>,
+    pub low_leaf_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for RevocationMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let revocation_root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "revocation_root"), || {
+                Ok(&self.revocation_root)
+            })?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (_, leaf) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        // Check the same leaf falls in a gap of the revocation tree
+        enforce_not_revoked::<C>(
+            cs,
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &revocation_root,
+            &leaf,
+            self.low_leaf.as_ref(),
+            self.low_leaf_path.as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit whose proofs are bound to a caller-supplied context, such
+/// as a message hash or a verifier nonce.
+///
+/// The member record stays private and, like in [`NullifierMerkleTreeCircuit`],
+/// the prover shows knowledge of the [`Identity`] behind its commitment, so only
+/// the member can sign. The context is an extra public input, so a proof cannot
+/// be replayed under another context.
+#[derive(Clone)]
+pub struct ContextMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub context: C::Field,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub identity: Option<Identity>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for ContextMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs. Groth16 binds every public input through its
+        // own verifying key term, so the context needs no constraints.
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let _context = FpVar::new_input(ark_relations::ns!(cs, "context"), || Ok(self.context))?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        let _ =
+            enforce_identity_opening::<C>(cs, &leaf_crh_params, &record, self.identity.as_ref())?;
+
+        Ok(())
+    }
+}
+
+/// Membership circuit that also outputs a nullifier, so each member can prove at
+/// most once per external nullifier (Semaphore-style).
+///
+/// The nullifier is [`member_nullifier`], `Blake2s(secret || external_nullifier)`,
+/// where `secret` is the member's [`Identity`]: the member record that is proven
+/// to be in the tree only holds its commitment `LeafHash(secret)`, which the
+/// circuit opens.
+///
+/// [`member_nullifier`]: super::common::member_nullifier
+#[derive(Clone)]
+pub struct NullifierMerkleTreeCircuit<C: PedersenCurve> {
+    // constants that will be embedded into the circuit
+    pub leaf_crh_params: <LeafHash<C> as CRH>::Parameters,
+    pub two_to_one_crh_params: <TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+
+    // These are the public inputs to the circuit
+    pub root: Root<C>,
+    pub external_nullifier: C::Field,
+    pub nullifier: C::Field,
+
+    // These are the private witnesses to the circuit
+    pub member: Option<Member>,
+    pub identity: Option<Identity>,
+    pub authentication_path: Option<MerklePath<C>>,
+}
+
+impl<C: PedersenCurve> ConstraintSynthesizer<C::Field> for NullifierMerkleTreeCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ark_relations::r1cs::ConstraintSystemRef<C::Field>,
+    ) -> ark_relations::r1cs::Result<()> {
+        // Allocate parameters as constants
+        let leaf_crh_params =
+            LeafHashParamsVar::<C>::new_constant(cs.clone(), self.leaf_crh_params)?;
+        let two_to_one_crh_params =
+            TwoToOneHashParamsVar::<C>::new_constant(cs.clone(), self.two_to_one_crh_params)?;
+
+        // Allocate public inputs
+        let root =
+            PedersenRootVar::<C>::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
+        let external_nullifier =
+            FpVar::new_input(ark_relations::ns!(cs, "external_nullifier"), || {
+                Ok(self.external_nullifier)
+            })?;
+        let nullifier =
+            FpVar::new_input(ark_relations::ns!(cs, "nullifier"), || Ok(self.nullifier))?;
+
+        // Recompute the leaf from the private member record and check its path
+        let (record, _) = enforce_member_in_tree::<C>(
+            cs.clone(),
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &root,
+            self.member.as_ref(),
+            self.authentication_path.as_ref(),
+        )?;
+
+        // Recompute the nullifier from the secret behind the record's commitment
+        let mut input =
+            enforce_identity_opening::<C>(cs, &leaf_crh_params, &record, self.identity.as_ref())?;
+        input.extend(external_nullifier.to_bytes()?);
+        let input_bits = input
+            .iter()
+            .map(UInt8::to_bits_le)
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        // The digest words are little-endian, like the bytes `Blake2s::digest`
+        // returns, and their bits sum to the digest reduced into the field
+        let mut computed_nullifier = FpVar::zero();
+        let mut power = C::Field::one();
+        for bit in evaluate_blake2s(&input_bits)?
+            .iter()
+            .flat_map(UInt32::to_bits_le)
+        {
+            computed_nullifier += FpVar::from(bit) * power;
+            power.double_in_place();
+        }
+
+        computed_nullifier.enforce_equal(&nullifier)?;
+
+        Ok(())
+    }
+}
+
+/// Allocates `member` and its authentication path as witnesses and enforces that
+/// the record hashes, exactly like `Member::hash`, to a leaf under `root`.
+/// Returns the record bytes so callers can constrain its fields, and the leaf.
+fn enforce_member_in_tree<C: PedersenCurve>(
+    cs: ConstraintSystemRef<C::Field>,
+    leaf_crh_params: &LeafHashParamsVar<C>,
+    two_to_one_crh_params: &TwoToOneHashParamsVar<C>,
+    root: &PedersenRootVar<C>,
+    member: Option<&Member>,
+    authentication_path: Option<&MerklePath<C>>,
+) -> Result<(MemberRecordVar<C>, PedersenLeafVar<C>), SynthesisError> {
+    let record = member_record_witness::<C>(cs.clone(), member)?;
+
+    let path: PedersenPathVar<C> =
+        PedersenPathVar::<C>::new_witness(ark_relations::ns!(cs, "path_witness"), || {
+            authentication_path.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+    let hashed_leaf: PedersenLeafVar<C> = <LeafHashGadget<C> as CRHGadget<
+        LeafHash<C>,
+        C::Field,
+    >>::evaluate(leaf_crh_params, &record)?;
+
+    let is_member: Boolean<C::Field> =
+        path.verify_membership(leaf_crh_params, two_to_one_crh_params, root, &hashed_leaf)?;
+
+    is_member.enforce_equal(&Boolean::TRUE)?;
+
+    Ok((record, hashed_leaf))
+}
+
+/// Allocates the secret of `identity` as private witness bytes and enforces that
+/// `record` holds its commitment, laid out like `Member::with_identity_commitment`
+/// stores `Identity::commitment`. Returns the secret bytes.
+fn enforce_identity_opening<C: PedersenCurve>(
+    cs: ConstraintSystemRef<C::Field>,
+    leaf_crh_params: &LeafHashParamsVar<C>,
+    record: &MemberRecordVar<C>,
+    identity: Option<&Identity>,
+) -> Result<Vec<UInt8<C::Field>>, SynthesisError> {
+    let secret = (0..MEMBER_SECRET_BYTES)
+        .map(|i| {
+            UInt8::new_witness(ark_relations::ns!(cs, "identity_secret"), || {
+                identity
+                    .map(|identity| identity.secret()[i])
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut commitment = <LeafHashGadget<C> as CRHGadget<LeafHash<C>, C::Field>>::evaluate(
+        leaf_crh_params,
+        &secret,
+    )?
+    .to_bytes()?;
+    commitment.resize(IDENTITY_COMMITMENT_BYTES, UInt8::constant(0));
+
+    record[IDENTITY_COMMITMENT_FLAG_OFFSET].enforce_equal(&UInt8::constant(1))?;
+    record[IDENTITY_COMMITMENT_OFFSET..IDENTITY_COMMITMENT_OFFSET + IDENTITY_COMMITMENT_BYTES]
+        .enforce_equal(&commitment)?;
+
+    Ok(secret)
+}
+
+/// Allocates the low leaf of `value` in a [`RevocationTree`] and its path as
+/// witnesses, and enforces that the leaf is under `revocation_root` and that its
+/// gap covers `value`, i.e. that `value` is not revoked.
+fn enforce_not_revoked<C: PedersenCurve>(
+    cs: ConstraintSystemRef<C::Field>,
+    leaf_crh_params: &LeafHashParamsVar<C>,
+    two_to_one_crh_params: &TwoToOneHashParamsVar<C>,
+    revocation_root: &PedersenRootVar<C>,
+    value: &FpVar<C::Field>,
+    low_leaf: Option<&RevocationLeaf<C>>,
+    low_leaf_path: Option<&MerklePath<C>>,
+) -> Result<(), SynthesisError> {
+    let low_value = FpVar::new_witness(ark_relations::ns!(cs, "low_value"), || {
+        low_leaf
+            .map(|leaf| leaf.value)
+            .ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    let next_value = FpVar::new_witness(ark_relations::ns!(cs, "next_value"), || {
+        low_leaf
+            .map(|leaf| leaf.next_value)
+            .ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    let path: PedersenPathVar<C> =
+        PedersenPathVar::<C>::new_witness(ark_relations::ns!(cs, "low_leaf_path"), || {
+            low_leaf_path.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+    // Same bytes as `RevocationLeaf::write`
+    let mut leaf = low_value.to_bytes()?;
+    leaf.extend(next_value.to_bytes()?);
+    path.verify_membership(
+        leaf_crh_params,
+        two_to_one_crh_params,
+        revocation_root,
+        &leaf.as_slice(),
+    )?
+    .enforce_equal(&Boolean::TRUE)?;
+
+    // low_value < value < next_value, where a zero next_value stands for infinity
+    is_less_than(&low_value, value)?.enforce_equal(&Boolean::TRUE)?;
+    is_less_than(value, &next_value)?
+        .or(&next_value.is_zero()?)?
+        .enforce_equal(&Boolean::TRUE)?;
+
+    Ok(())
+}
+
+/// Allocates `member.to_bytes().unwrap()`, zero-padded to
+/// [`PedersenCurve::MEMBER_RECORD_BYTES`], as private witness bytes. The padding
+/// matches the one `LeafHash` applies natively.
+fn member_record_witness<C: PedersenCurve>(
+    cs: ConstraintSystemRef<C::Field>,
+    member: Option<&Member>,
+) -> Result<MemberRecordVar<C>, SynthesisError> {
+    let record = member
+        .map(Member::to_bytes)
+        .transpose()
+        .map_err(|_| SynthesisError::Unsatisfiable)?;
+    if record
+        .as_ref()
+        .is_some_and(|record| record.len() > C::MEMBER_RECORD_BYTES)
+    {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    (0..C::MEMBER_RECORD_BYTES)
+        .map(|i| {
+            UInt8::new_witness(ark_relations::ns!(cs, "member_record"), || {
+                record
+                    .as_ref()
+                    .map(|record| record.get(i).copied().unwrap_or(0))
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::pedersen::common::{
+        empty_leaf, member_nullifier, new_membership_tree, MembershipTree, RevocationTree,
+        RootHistory,
+    };
+
+    /// Tree over `members` as `new_membership_tree` pads it, with the leaves
+    /// including the padding.
+    fn padded_tree<C: PedersenCurve>(
+        leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+        two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+        members: &[Member],
+    ) -> (MembershipTree<C>, Vec<C::Field>) {
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(leaf_crh_params, two_to_one_crh_params, &mut leaves);
+        (tree, leaves)
+    }
+
+    fn check_anonymous<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [Identity::random(), Identity::random()];
+        let commitment = |identity: &Identity| {
+            identity
+                .commitment::<LeafHash<C>>(&leaf_crh_params)
+                .unwrap()
+        };
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[0])),
+            Member::new("2".into(), "2@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[1])),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+        ];
+        let (tree, _) = padded_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &members);
+
+        let circuit = AnonymousMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params,
+            root: tree.root(),
+            member: Some(members[1].clone()),
+            identity: Some(identities[1].clone()),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+
+        // Only the constant `one` and the root are instance variables
+        assert_eq!(cs.num_instance_variables(), 2);
+        assert!(cs.is_satisfied().unwrap());
+
+        // A record that does not sit at the end of the path must be rejected
+        let cs = ConstraintSystem::new_ref();
+        AnonymousMerkleTreeCircuit::<C> {
+            member: Some(members[0].clone()),
+            ..circuit.clone()
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // Knowing a leaf is not enough without the secret behind its commitment
+        let cs = ConstraintSystem::new_ref();
+        AnonymousMerkleTreeCircuit::<C> {
+            identity: Some(identities[0].clone()),
+            ..circuit.clone()
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // A record without an identity commitment proves nothing, and neither
+        // does a forged record at a padding leaf
+        for (member, index) in [(members[2].clone(), 2), (Member::default(), 3)] {
+            let cs = ConstraintSystem::new_ref();
+            AnonymousMerkleTreeCircuit::<C> {
+                member: Some(member),
+                identity: Some(Identity::random()),
+                authentication_path: Some(tree.generate_proof(index).unwrap()),
+                ..circuit.clone()
+            }
+            .generate_constraints(cs.clone())
+            .unwrap();
+            assert!(!cs.is_satisfied().unwrap());
+        }
+    }
+
+    fn check_member<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("2".into(), "2@usc.edu".into(), None),
+        ];
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+
+        let circuit = MemberMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params,
+            root: tree.root(),
+            member: Some(members[1].clone()),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert_eq!(cs.num_instance_variables(), 2);
+        assert!(cs.is_satisfied().unwrap());
+
+        // Padding has no known record, so the empty one does not open it
+        let (padded, padded_leaves) = padded_tree::<C>(
+            &leaf_crh_params,
+            &circuit.two_to_one_crh_params,
+            &[
+                members[0].clone(),
+                members[1].clone(),
+                Member::new("3".into(), "3@usc.edu".into(), None),
+            ],
+        );
+        assert_eq!(padded_leaves[3], empty_leaf::<C>());
+        let padding = MemberMerkleTreeCircuit::<C> {
+            root: padded.root(),
+            member: Some(Member::default()),
+            authentication_path: Some(padded.generate_proof(3).unwrap()),
+            ..circuit.clone()
+        };
+        let cs = ConstraintSystem::new_ref();
+        padding.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // A record that was never registered does not hash to a leaf of the tree
+        let circuit = MemberMerkleTreeCircuit::<C> {
+            member: Some(Member::new("3".into(), "3@usc.edu".into(), None)),
+            ..circuit
+        };
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_nullifier<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [Identity::random(), Identity::random()];
+        let commitment = |identity: &Identity| {
+            identity
+                .commitment::<LeafHash<C>>(&leaf_crh_params)
+                .unwrap()
+        };
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[0])),
+            Member::new("2".into(), "2@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[1])),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+            Member::new("4".into(), "4@usc.edu".into(), None),
+        ];
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+
+        let poll = C::Field::from(7u64);
+        let nullifier = member_nullifier::<C>(&identities[1], &poll);
+
+        // Nullifiers are bound to the context they were derived for
+        assert_ne!(
+            nullifier,
+            member_nullifier::<C>(&identities[1], &C::Field::from(8u64))
+        );
+
+        let circuit = NullifierMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root: tree.root(),
+            external_nullifier: poll,
+            nullifier,
+            member: Some(members[1].clone()),
+            identity: Some(identities[1].clone()),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // Another member's nullifier cannot be claimed
+        let cs = ConstraintSystem::new_ref();
+        NullifierMerkleTreeCircuit::<C> {
+            nullifier: member_nullifier::<C>(&identities[0], &poll),
+            ..circuit.clone()
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // Nor can another identity be used with this member's record
+        let cs = ConstraintSystem::new_ref();
+        NullifierMerkleTreeCircuit::<C> {
+            nullifier: member_nullifier::<C>(&identities[0], &poll),
+            identity: Some(identities[0].clone()),
+            ..circuit.clone()
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // A record without an identity commitment cannot derive nullifiers
+        let identity = Identity::random();
+        let cs = ConstraintSystem::new_ref();
+        NullifierMerkleTreeCircuit::<C> {
+            nullifier: member_nullifier::<C>(&identity, &poll),
+            member: Some(members[2].clone()),
+            identity: Some(identity),
+            authentication_path: Some(tree.generate_proof(2).unwrap()),
+            ..circuit
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_context<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [Identity::random(), Identity::random()];
+        let commitment = |identity: &Identity| {
+            identity
+                .commitment::<LeafHash<C>>(&leaf_crh_params)
+                .unwrap()
+        };
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[0])),
+            Member::new("2".into(), "2@usc.edu".into(), None)
+                .with_identity_commitment(commitment(&identities[1])),
+        ];
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+
+        let circuit = ContextMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params,
+            root: tree.root(),
+            context: crate::context::message_context(b"nonce"),
+            member: Some(members[0].clone()),
+            identity: Some(identities[0].clone()),
+            authentication_path: Some(tree.generate_proof(0).unwrap()),
+        };
+
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+
+        // `one`, the root and the context
+        assert_eq!(cs.num_instance_variables(), 3);
+        assert!(cs.is_satisfied().unwrap());
+
+        // Knowing a leaf is not enough without the secret behind its commitment
+        let circuit = ContextMerkleTreeCircuit::<C> {
+            identity: Some(identities[1].clone()),
+            ..circuit
+        };
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_expiry<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+        use chrono::{DateTime, Utc};
+
+        let at = |date: &str| {
+            DateTime::parse_from_rfc3339(date)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new_with_join_date(
+                "1".into(),
+                "1@usc.edu".into(),
+                at("2023-01-01T00:00:00Z"),
+                Some(at("2024-01-01T00:00:00Z")),
+            ),
+            Member::new_with_join_date(
+                "2".into(),
+                "2@usc.edu".into(),
+                at("2023-01-01T00:00:00Z"),
+                None,
+            ),
+        ];
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+
+        for (index, now) in [
+            (0, "2023-06-01T00:00:00Z"),
+            (0, "2022-12-31T23:59:59Z"),
+            (0, "2024-01-01T00:00:00Z"),
+            (1, "2023-06-01T00:00:00Z"),
+            (1, "2030-01-01T00:00:00Z"),
+        ] {
+            let now = at(now);
+            let circuit = ExpiryMerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                now,
+                member: Some(members[index].clone()),
+                authentication_path: Some(tree.generate_proof(index).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(
+                cs.is_satisfied().unwrap(),
+                members[index].is_active_at(&now),
+                "member {} at {}",
+                index,
+                now
+            );
+        }
+
+        // A record without an end date would be active forever, but no record
+        // opens the padding of a tree whose size is not a power of two
+        let (padded, _) = padded_tree::<C>(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            &[
+                members[0].clone(),
+                members[1].clone(),
+                Member::new("3".into(), "3@usc.edu".into(), None),
+            ],
+        );
+        let forged = Member::new_with_join_date(
+            String::new(),
+            String::new(),
+            at("2023-01-01T00:00:00Z"),
+            None,
+        );
+        let now = at("2030-01-01T00:00:00Z");
+        assert!(forged.is_active_at(&now));
+        let circuit = ExpiryMerkleTreeCircuit::<C> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: padded.root(),
+            now,
+            member: Some(forged),
+            authentication_path: Some(padded.generate_proof(3).unwrap()),
+        };
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_seniority<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+        use chrono::{DateTime, Duration, Utc};
+
+        let at = |date: &str| {
+            DateTime::parse_from_rfc3339(date)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let member = Member::new_with_join_date(
+            "1".into(),
+            "1@usc.edu".into(),
+            at("2023-01-01T00:00:00Z"),
+            None,
+        );
+        let (tree, _) = padded_tree::<C>(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            std::slice::from_ref(&member),
+        );
+
+        let now = at("2023-06-01T00:00:00Z");
+        for (min_seniority, expected) in [
+            (Duration::days(30), true),
+            (now - at("2023-01-01T00:00:00Z"), true),
+            (Duration::days(365), false),
+        ] {
+            let circuit = SeniorityMerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                now,
+                min_seniority,
+                member: Some(member.clone()),
+                authentication_path: Some(tree.generate_proof(0).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+
+        // The padding leaf next to the member cannot be opened to a record of
+        // any seniority
+        let circuit = SeniorityMerkleTreeCircuit::<C> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: tree.root(),
+            now,
+            min_seniority: Duration::days(30),
+            member: Some(Member::new_with_join_date(
+                String::new(),
+                String::new(),
+                at("2000-01-01T00:00:00Z"),
+                None,
+            )),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_email_domain<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("22".into(), "alice@cs.usc.edu".into(), None),
+        ];
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+
+        for (index, domain) in [
+            (0, "usc.edu"),
+            (0, "sc.edu"),
+            (0, "ucla.edu"),
+            (1, "cs.usc.edu"),
+            (1, "usc.edu"),
+        ] {
+            let circuit = EmailDomainMerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                domain: domain.into(),
+                member: Some(members[index].clone()),
+                authentication_path: Some(tree.generate_proof(index).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(
+                cs.is_satisfied().unwrap(),
+                members[index].has_email_domain(domain),
+                "{} in {}",
+                domain,
+                members[index].email()
+            );
+        }
+    }
+
+    fn check_revocation<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members =
+            [1u64, 2, 3, 4].map(|i| Member::new(i.to_string(), format!("{}@usc.edu", i), None));
+        let leaves = members
+            .clone()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let tree =
+            MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
+        let revocations =
+            RevocationTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &[leaves[2]]);
+
+        for (index, member) in members.iter().enumerate() {
+            // A revoked member can only try to reuse the gap of another leaf
+            let (low_leaf, low_leaf_path) = revocations
+                .prove_non_membership(&leaves[index])
+                .unwrap_or_else(|| revocations.prove_non_membership(&leaves[0]).unwrap());
+
+            let circuit = RevocationMerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                revocation_root: revocations.root(),
+                member: Some(member.clone()),
+                authentication_path: Some(tree.generate_proof(index).unwrap()),
+                low_leaf: Some(low_leaf),
+                low_leaf_path: Some(low_leaf_path),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), index != 2);
+        }
+    }
+
+    fn check_batch<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [1u64, 2, 3, 4].map(|_| Identity::random());
+        let members = [1u64, 2, 3, 4]
+            .iter()
+            .zip(&identities)
+            .map(|(i, identity)| {
+                Member::new(i.to_string(), format!("{}@usc.edu", i), None).with_identity_commitment(
+                    identity
+                        .commitment::<LeafHash<C>>(&leaf_crh_params)
+                        .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (tree, _) = padded_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &members);
+
+        let circuit_for = |indices: &[usize]| BatchMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root: tree.root(),
+            members: indices.iter().map(|&i| Some(members[i].clone())).collect(),
+            identities: indices
+                .iter()
+                .map(|&i| Some(identities[i].clone()))
+                .collect(),
+            authentication_paths: indices
+                .iter()
+                .map(|&i| Some(tree.generate_proof(i).unwrap()))
+                .collect(),
+        };
+
+        for (indices, expected) in [(vec![0, 2, 3], true), (vec![1, 3, 1], false)] {
+            // Repeated leaves have no witness for their difference's inverse
+            let cs = ConstraintSystem::new_ref();
+            let satisfied = circuit_for(&indices)
+                .generate_constraints(cs.clone())
+                .is_ok()
+                && cs.is_satisfied().unwrap();
+            assert_eq!(satisfied, expected);
+        }
+
+        // A record with another record's path is rejected
+        let mut circuit = circuit_for(&[0, 1]);
+        circuit.authentication_paths[1] = Some(tree.generate_proof(2).unwrap());
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // Every officer in the batch must know the secret behind their record
+        let mut circuit = circuit_for(&[0, 1]);
+        circuit.identities[1] = Some(identities[0].clone());
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_multi_root<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identity = Identity::random();
+        let commitment = identity
+            .commitment::<LeafHash<C>>(&leaf_crh_params)
+            .unwrap();
+
+        // One tree per organization
+        let trees = ["usc.edu", "ucla.edu", "mit.edu"].map(|domain| {
+            let members = [1u64, 2]
+                .map(|i| Member::new(i.to_string(), format!("{}@{}", i, domain), None))
+                .map(|member| member.with_identity_commitment(commitment));
+            let (tree, _) = padded_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &members);
+            (members, tree)
+        });
+        let roots = trees
+            .iter()
+            .map(|(_, tree)| tree.root())
+            .collect::<Vec<_>>();
+
+        let (members, tree) = &trees[1];
+        let circuit = MultiRootMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            roots: roots.clone(),
+            selector: Some(1),
+            member: Some(members[1].clone()),
+            identity: Some(identity.clone()),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+        for (selector, expected) in [(1, true), (0, false), (2, false), (3, false)] {
+            let cs = ConstraintSystem::new_ref();
+            MultiRootMerkleTreeCircuit::<C> {
+                selector: Some(selector),
+                ..circuit.clone()
+            }
+            .generate_constraints(cs.clone())
+            .unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+
+        // Knowing a leaf is not enough without the secret behind its commitment
+        let cs = ConstraintSystem::new_ref();
+        MultiRootMerkleTreeCircuit::<C> {
+            identity: Some(Identity::random()),
+            ..circuit
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    fn check_history<C: PedersenCurve>() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identity = Identity::random();
+        let member = Member::new("1".into(), "1@usc.edu".into(), None).with_identity_commitment(
+            identity
+                .commitment::<LeafHash<C>>(&leaf_crh_params)
+                .unwrap(),
+        );
+
+        // Every registration produces a new root
+        let mut members = vec![member.clone()];
+        let mut trees = vec![];
+        for i in 2..=5 {
+            let mut leaves = members
+                .iter()
+                .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+                .collect::<Vec<_>>();
+            leaves.resize(4, empty_leaf::<C>());
+            let tree = MembershipTree::<C>::new(&leaf_crh_params, &two_to_one_crh_params, &leaves)
+                .unwrap();
+            trees.push(tree);
+            members.push(Member::new(i.to_string(), format!("{}@usc.edu", i), None));
+        }
+
+        let mut history = RootHistory::<C>::new(2, trees[0].root());
+        for tree in &trees[1..] {
+            history.push(tree.root());
+        }
+        let history_root = history.commitment(&leaf_crh_params, &two_to_one_crh_params);
+
+        // Proofs against the last two roots are accepted, older ones are not
+        for (i, tree) in trees.iter().enumerate() {
+            let root = tree.root();
+            let history_path = history
+                .generate_proof(&leaf_crh_params, &two_to_one_crh_params, &root)
+                .unwrap_or_else(|| {
+                    history
+                        .generate_proof(&leaf_crh_params, &two_to_one_crh_params, &trees[3].root())
+                        .unwrap()
+                });
+
+            let circuit = HistoryMerkleTreeCircuit::<C> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                history_root,
+                root: Some(root),
+                history_path: Some(history_path),
+                member: Some(member.clone()),
+                identity: Some(identity.clone()),
+                authentication_path: Some(tree.generate_proof(0).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.clone().generate_constraints(cs.clone()).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), i >= 2);
+
+            // Knowing a leaf is not enough without the secret behind its commitment
+            let cs = ConstraintSystem::new_ref();
+            HistoryMerkleTreeCircuit::<C> {
+                identity: Some(Identity::random()),
+                ..circuit
+            }
+            .generate_constraints(cs.clone())
+            .unwrap();
+            assert!(!cs.is_satisfied().unwrap());
+        }
+    }
+
+    all_curves_tests! {
+        anonymous_merkle_tree_constraints => check_anonymous,
+        member_merkle_tree_constraints => check_member,
+        nullifier_merkle_tree_constraints => check_nullifier,
+        context_merkle_tree_constraints => check_context,
+        expiry_merkle_tree_constraints => check_expiry,
+        seniority_merkle_tree_constraints => check_seniority,
+        email_domain_merkle_tree_constraints => check_email_domain,
+        revocation_merkle_tree_constraints => check_revocation,
+        batch_merkle_tree_constraints => check_batch,
+        multi_root_merkle_tree_constraints => check_multi_root,
+        history_merkle_tree_constraints => check_history,
+    }
+}
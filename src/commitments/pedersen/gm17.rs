@@ -0,0 +1,123 @@
+//! GM17 backend for [`MerkleTreeCircuit`]. Unlike Groth16, GM17 proofs are
+//! simulation extractable, so a proof cannot be mauled into another valid proof.
+
+use super::common::Root;
+use super::constraint::MerkleTreeCircuit;
+use super::PedersenCurve;
+use ark_crypto_primitives::SNARK;
+use ark_gm17::{Proof, ProvingKey, VerifyingKey, GM17};
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Proving and verifying key of one circuit.
+type KeyPair<E> = (ProvingKey<E>, VerifyingKey<E>);
+
+/// Generates circuit-specific GM17 keys for the shape of `circuit`.
+pub fn setup<C: PedersenCurve, R: RngCore + CryptoRng>(
+    circuit: MerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<KeyPair<C::Pairing>, SynthesisError> {
+    GM17::<C::Pairing>::circuit_specific_setup(circuit, rng)
+}
+
+/// Proves that the circuit's leaf is a member of the tree under its root.
+pub fn prove<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: MerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    GM17::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove`] against the public inputs of
+/// [`MerkleTreeCircuit`].
+pub fn verify<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    root: &Root<C>,
+    leaf_hash: &C::Field,
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    GM17::<C::Pairing>::verify(vk, &[*root, *leaf_hash], proof)
+}
+
+/// Compressed encoding of a GM17 proof.
+pub fn proof_to_bytes<C: PedersenCurve>(
+    proof: &Proof<C::Pairing>,
+) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = Vec::with_capacity(proof.serialized_size());
+    proof.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes a proof encoded by [`proof_to_bytes`], checking that its points are
+/// on the curve and in the right subgroup.
+pub fn proof_from_bytes<C: PedersenCurve>(
+    bytes: &[u8],
+) -> Result<Proof<C::Pairing>, SerializationError> {
+    Proof::deserialize(bytes)
+}
+
+/// Compressed encoding of a GM17 verifying key.
+pub fn verifying_key_to_bytes<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = Vec::with_capacity(vk.serialized_size());
+    vk.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes a verifying key encoded by [`verifying_key_to_bytes`].
+pub fn verifying_key_from_bytes<C: PedersenCurve>(
+    bytes: &[u8],
+) -> Result<VerifyingKey<C::Pairing>, SerializationError> {
+    VerifyingKey::deserialize(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::pedersen::common::{new_membership_tree, LeafHash, TwoToOneHash};
+    use crate::member::Member;
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+
+    fn check_gm17_round_trip<C: PedersenCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let circuit = MerkleTreeCircuit::<C> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root,
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) = setup::<C, _>(circuit.clone(), &mut rng).unwrap();
+        let proof = prove::<C, _>(&pk, circuit, &mut rng).unwrap();
+
+        // Proof and key survive a round trip through their encodings
+        let proof = proof_from_bytes::<C>(&proof_to_bytes::<C>(&proof).unwrap()).unwrap();
+        let vk = verifying_key_from_bytes::<C>(&verifying_key_to_bytes::<C>(&vk).unwrap()).unwrap();
+
+        assert!(verify::<C>(&vk, &root, &leaves[1], &proof).unwrap());
+        assert!(!verify::<C>(&vk, &root, &leaves[0], &proof).unwrap());
+    }
+
+    all_curves_tests! {
+        gm17_round_trip => check_gm17_round_trip,
+    }
+}
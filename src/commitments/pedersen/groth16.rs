@@ -0,0 +1,329 @@
+use super::common::{LeafHash, Root, TwoToOneHash};
+use super::constraint::{
+    AnonymousMerkleTreeCircuit, ContextMerkleTreeCircuit, HistoryMerkleTreeCircuit,
+    MerkleTreeCircuit, MultiRootMerkleTreeCircuit, NullifierMerkleTreeCircuit,
+};
+use super::PedersenCurve;
+use crate::nullifier::NullifierStore;
+use ark_crypto_primitives::{crh::TwoToOneCRH, CircuitSpecificSetupSNARK, CRH, SNARK};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Proving and verifying key of one circuit.
+type KeyPair<E> = (ProvingKey<E>, VerifyingKey<E>);
+
+/// Sets up keys for the [`MerkleTreeCircuit`] of trees of `depth`. The
+/// circuit's shape depends only on the depth, so the keys prove membership in
+/// a [`FixedDepthTree`](super::common::FixedDepthTree) of that depth at every
+/// member count.
+pub fn setup_for_depth<C: PedersenCurve, R: RngCore + CryptoRng>(
+    leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    depth: usize,
+    rng: &mut R,
+) -> Result<KeyPair<C::Pairing>, SynthesisError> {
+    let circuit = MerkleTreeCircuit::<C>::placeholder(
+        leaf_crh_params.clone(),
+        two_to_one_crh_params.clone(),
+        depth,
+    );
+    Groth16::<C::Pairing>::setup(circuit, rng)
+}
+
+/// Proves membership without revealing which leaf belongs to the prover.
+pub fn prove_anonymous<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: AnonymousMerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    Groth16::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_anonymous`] against `root`, the only
+/// public input of [`AnonymousMerkleTreeCircuit`].
+pub fn verify_anonymous<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    root: &Root<C>,
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    Groth16::<C::Pairing>::verify(vk, &[*root], proof)
+}
+
+/// Proves membership under any root of a [`super::common::RootHistory`], so the
+/// proof survives registrations that happen while it is being generated.
+pub fn prove_with_history<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: HistoryMerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    Groth16::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_history`] against the current
+/// commitment of the root history.
+pub fn verify_with_history<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    history_root: &Root<C>,
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    Groth16::<C::Pairing>::verify(vk, &[*history_root], proof)
+}
+
+/// Proves membership in one of the circuit's roots without revealing which.
+pub fn prove_any_root<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: MultiRootMerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    Groth16::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_any_root`]. `roots` must be given in
+/// the same order, and have the same length, as at setup time.
+pub fn verify_any_root<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    roots: &[Root<C>],
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    Groth16::<C::Pairing>::verify(vk, roots, proof)
+}
+
+/// Proves membership bound to the circuit's context, e.g. a message hash or a
+/// verifier nonce (see [`crate::context::message_context`]).
+pub fn prove_with_context<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: ContextMerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    Groth16::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_context`]; it only verifies under
+/// the context it was created for.
+pub fn verify_with_context<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    root: &Root<C>,
+    context: &C::Field,
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    Groth16::<C::Pairing>::verify(vk, &[*root, *context], proof)
+}
+
+/// Proves membership and derives the member's nullifier for the circuit's
+/// external nullifier.
+pub fn prove_with_nullifier<C: PedersenCurve, R: RngCore + CryptoRng>(
+    pk: &ProvingKey<C::Pairing>,
+    circuit: NullifierMerkleTreeCircuit<C>,
+    rng: &mut R,
+) -> Result<Proof<C::Pairing>, SynthesisError> {
+    Groth16::<C::Pairing>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_nullifier`] and records its
+/// nullifier in `store`. A nullifier that was already accepted for
+/// `external_nullifier` is rejected.
+pub fn verify_with_nullifier<C: PedersenCurve>(
+    vk: &VerifyingKey<C::Pairing>,
+    store: &mut NullifierStore<C::Field>,
+    root: &Root<C>,
+    external_nullifier: &C::Field,
+    nullifier: &C::Field,
+    proof: &Proof<C::Pairing>,
+) -> Result<bool, SynthesisError> {
+    store.verify_and_insert(*external_nullifier, *nullifier, || {
+        Groth16::<C::Pairing>::verify(vk, &[*root, *external_nullifier, *nullifier], proof)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::pedersen::common::{
+        member_nullifier, new_membership_tree, MembershipTreeBuilder,
+    };
+    use crate::member::{Identity, Member};
+
+    fn check_anonymous_proofs_share_public_inputs<C: PedersenCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [Identity::random(), Identity::random(), Identity::random()];
+        let members = ["alice", "bob", "carol"]
+            .iter()
+            .zip(&identities)
+            .map(|(name, identity)| {
+                Member::new(name.to_string(), format!("{}@usc.edu", name), None)
+                    .with_identity_commitment(
+                        identity
+                            .commitment::<LeafHash<C>>(&leaf_crh_params)
+                            .unwrap(),
+                    )
+            })
+            .collect::<Vec<_>>();
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let circuit_for = |index: usize| AnonymousMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root,
+            member: Some(members[index].clone()),
+            identity: Some(identities[index].clone()),
+            authentication_path: Some(tree.generate_proof(index).unwrap()),
+        };
+
+        let (pk, vk) =
+            Groth16::<C::Pairing>::circuit_specific_setup(circuit_for(0), &mut rng).unwrap();
+
+        let alice_proof = prove_anonymous::<C, _>(&pk, circuit_for(0), &mut rng).unwrap();
+        let bob_proof = prove_anonymous::<C, _>(&pk, circuit_for(1), &mut rng).unwrap();
+
+        // Both proofs are checked against exactly the same statement: the root
+        assert!(verify_anonymous::<C>(&vk, &root, &alice_proof).unwrap());
+        assert!(verify_anonymous::<C>(&vk, &root, &bob_proof).unwrap());
+    }
+
+    fn check_one_key_serves_every_tree_size<C: PedersenCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let (pk, vk) =
+            setup_for_depth::<C, _>(&leaf_crh_params, &two_to_one_crh_params, 3, &mut rng).unwrap();
+
+        let leaves = [1u64, 2, 3, 4, 5]
+            .map(|i| Member::new(i.to_string(), format!("{}@usc.edu", i), None))
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap());
+        let builder = MembershipTreeBuilder::<C>::new(leaf_crh_params, two_to_one_crh_params, 3);
+
+        // Member counts on both sides of a power of two share the key
+        for count in [1, 2, 3, 5] {
+            let tree = builder.build(&leaves[..count]).unwrap();
+            let index = count - 1;
+            let circuit = MerkleTreeCircuit::for_member(&tree, index).unwrap();
+
+            let proof = Groth16::<C::Pairing>::prove(&pk, circuit, &mut rng).unwrap();
+            assert!(
+                Groth16::<C::Pairing>::verify(&vk, &[tree.root(), leaves[index]], &proof).unwrap()
+            );
+        }
+    }
+
+    fn check_nullifier_can_only_be_used_once<C: PedersenCurve>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let identities = [Identity::random(), Identity::random()];
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None).with_identity_commitment(
+                identities[0]
+                    .commitment::<LeafHash<C>>(&leaf_crh_params)
+                    .unwrap(),
+            ),
+            Member::new("bob".into(), "bob@usc.edu".into(), None).with_identity_commitment(
+                identities[1]
+                    .commitment::<LeafHash<C>>(&leaf_crh_params)
+                    .unwrap(),
+            ),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let poll = C::Field::from(2025u64);
+        let nullifier = member_nullifier::<C>(&identities[0], &poll);
+
+        let circuit = NullifierMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root,
+            external_nullifier: poll,
+            nullifier,
+            member: Some(members[0].clone()),
+            identity: Some(identities[0].clone()),
+            authentication_path: Some(tree.generate_proof(0).unwrap()),
+        };
+
+        let (pk, vk) =
+            Groth16::<C::Pairing>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        let proof = prove_with_nullifier::<C, _>(&pk, circuit, &mut rng).unwrap();
+
+        let mut store = NullifierStore::new();
+
+        // A forged nullifier neither verifies nor gets recorded
+        let forged = member_nullifier::<C>(&identities[1], &poll);
+        assert!(
+            !verify_with_nullifier::<C>(&vk, &mut store, &root, &poll, &forged, &proof).unwrap()
+        );
+        assert!(store.is_empty());
+
+        assert!(
+            verify_with_nullifier::<C>(&vk, &mut store, &root, &poll, &nullifier, &proof).unwrap()
+        );
+        assert!(
+            !verify_with_nullifier::<C>(&vk, &mut store, &root, &poll, &nullifier, &proof).unwrap()
+        );
+    }
+
+    fn check_context_proofs_cannot_be_replayed<C: PedersenCurve>() {
+        use crate::context::message_context;
+
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let bob = Identity::random();
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None)
+                .with_identity_commitment(bob.commitment::<LeafHash<C>>(&leaf_crh_params).unwrap()),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let challenge = message_context(b"session 1");
+        let circuit = ContextMerkleTreeCircuit::<C> {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root,
+            context: challenge,
+            member: Some(members[1].clone()),
+            identity: Some(bob),
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) =
+            Groth16::<C::Pairing>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        let proof = prove_with_context::<C, _>(&pk, circuit, &mut rng).unwrap();
+
+        assert!(verify_with_context::<C>(&vk, &root, &challenge, &proof).unwrap());
+
+        let next_challenge = message_context(b"session 2");
+        assert!(!verify_with_context::<C>(&vk, &root, &next_challenge, &proof).unwrap());
+    }
+
+    all_curves_tests! {
+        anonymous_proofs_share_public_inputs => check_anonymous_proofs_share_public_inputs,
+        one_key_serves_every_tree_size => check_one_key_serves_every_tree_size,
+        nullifier_can_only_be_used_once => check_nullifier_can_only_be_used_once,
+        context_proofs_cannot_be_replayed => check_context_proofs_cannot_be_replayed,
+    }
+}
@@ -0,0 +1,111 @@
+/// Expands each `test => check` pair into a test that runs the curve-generic
+/// `check` for every [`PedersenCurve`] of the crate.
+#[cfg(test)]
+macro_rules! all_curves_tests {
+    ($($test:ident => $check:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                $check::<crate::commitments::pedersen254::EdOnBn254>();
+                $check::<crate::commitments::pedersen377::EdOnBls12_377>();
+                $check::<crate::commitments::pedersen381::EdOnBls12_381>();
+                $check::<crate::commitments::pedersen761::EdOnBw6_761>();
+            }
+        )*
+    };
+}
+
+pub mod common;
+pub mod constraint;
+pub mod gm17;
+pub mod groth16;
+
+pub use common::*;
+pub use constraint::*;
+pub use groth16::*;
+
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::{PrimeField, SquareRootField};
+use core::fmt::Debug;
+use core::hash::Hash;
+
+/// Bits per Pedersen window, shared by the leaf and two-to-one hashes.
+pub const WINDOW_SIZE: usize = 4;
+
+/// Curve choice of the Pedersen backend: the twisted Edwards curve the Pedersen
+/// generators live on, the pairing curve whose scalar field is that curve's base
+/// field, and the number of windows of each hash.
+///
+/// Implementors are marker types such as
+/// [`EdOnBls12_381`](super::pedersen381::EdOnBls12_381); every circuit, tree and
+/// Groth16 helper of the backend is generic over them.
+pub trait PedersenCurve: Clone + Copy + Debug + Default + PartialEq + Eq + Hash {
+    /// Embedded twisted Edwards curve.
+    type Parameters: TEModelParameters<BaseField = Self::Field>;
+    /// Circuit field: the base field of the Edwards curve.
+    type Field: PrimeField + SquareRootField;
+    /// Pairing curve proofs are created with.
+    type Pairing: PairingEngine<Fr = Self::Field>;
+
+    /// Number of windows of the leaf hash. It bounds the length of a serialized
+    /// [`Member`](crate::member::Member) record.
+    const LEAF_NUM_WINDOWS: usize;
+    /// Number of windows of the two-to-one hash. Both children are serialized
+    /// field elements, so it must cover twice their width.
+    const TWO_TO_ONE_NUM_WINDOWS: usize;
+
+    /// Maximum length of a serialized [`Member`](crate::member::Member) record
+    /// that the leaf hash can absorb.
+    const MEMBER_RECORD_BYTES: usize = WINDOW_SIZE * Self::LEAF_NUM_WINDOWS / 8;
+}
+
+#[cfg(test)]
+mod groth16_tests {
+    use super::{new_membership_tree, LeafHash, MerkleTreeCircuit, PedersenCurve, TwoToOneHash};
+    use crate::commitments::{
+        pedersen254::EdOnBn254, pedersen377::EdOnBls12_377, pedersen381::EdOnBls12_381,
+        pedersen761::EdOnBw6_761,
+    };
+    use crate::member::Member;
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH, SNARK};
+    use ark_groth16::Groth16;
+
+    fn prove_and_verify<C: PedersenCurve>() -> bool {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<C> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<C> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<C>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree::<C>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        let circuit = MerkleTreeCircuit::<C> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: tree.root(),
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) =
+            Groth16::<C::Pairing>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        let proof = Groth16::<C::Pairing>::prove(&pk, circuit, &mut rng).unwrap();
+        Groth16::<C::Pairing>::verify(&vk, &[tree.root(), leaves[1]], &proof).unwrap()
+    }
+
+    // All curves are available side by side in one binary
+    #[test]
+    fn all_curves() {
+        assert!(prove_and_verify::<EdOnBn254>());
+        assert!(prove_and_verify::<EdOnBls12_377>());
+        assert!(prove_and_verify::<EdOnBls12_381>());
+        assert!(prove_and_verify::<EdOnBw6_761>());
+    }
+}
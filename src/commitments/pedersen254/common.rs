@@ -0,0 +1,61 @@
+use super::EdOnBn254;
+use crate::commitments::pedersen::{common as generic, PedersenCurve};
+use crate::member::Identity;
+use ark_crypto_primitives::CRH;
+
+/////////////////////////////
+
+pub type Pedersen254Field = <EdOnBn254 as PedersenCurve>::Field;
+
+/////////////////////////////
+
+pub type TwoToOneWindow = generic::TwoToOneWindow<EdOnBn254>;
+pub type TwoToOneHash = generic::TwoToOneHash<EdOnBn254>;
+pub type Root = generic::Root<EdOnBn254>;
+pub type TwoToOneHashGadget = generic::TwoToOneHashGadget<EdOnBn254>;
+
+pub type LeafWindow = generic::LeafWindow<EdOnBn254>;
+pub type LeafHash = generic::LeafHash<EdOnBn254>;
+pub type Leaf = generic::Leaf<EdOnBn254>;
+pub type LeafHashGadget = generic::LeafHashGadget<EdOnBn254>;
+
+/// Maximum length of a serialized [`Member`](crate::member::Member) record that
+/// `LeafHash` can absorb.
+pub const MEMBER_RECORD_BYTES: usize = <EdOnBn254 as PedersenCurve>::MEMBER_RECORD_BYTES;
+
+pub type LeafHashParamsVar = generic::LeafHashParamsVar<EdOnBn254>;
+pub type TwoToOneHashParamsVar = generic::TwoToOneHashParamsVar<EdOnBn254>;
+
+/////////////////////////////
+
+pub type MerkleConfig = generic::MerkleConfig<EdOnBn254>;
+pub type MerklePath = generic::MerklePath<EdOnBn254>;
+pub type MembershipTree = generic::MembershipTree<EdOnBn254>;
+pub type MembershipTreeBuilder = generic::MembershipTreeBuilder<EdOnBn254>;
+pub type FixedDepthTree = generic::FixedDepthTree<EdOnBn254>;
+
+pub type RevocationLeaf = generic::RevocationLeaf<EdOnBn254>;
+pub type RevocationTree = generic::RevocationTree<EdOnBn254>;
+pub type RootHistory = generic::RootHistory<EdOnBn254>;
+
+pub fn new_membership_tree(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as CRH>::Parameters,
+    leaves: &mut Vec<Pedersen254Field>,
+) -> MembershipTree {
+    generic::new_membership_tree::<EdOnBn254>(leaf_crh_params, two_to_one_crh_params, leaves)
+}
+
+/// Leaf that pads a [`FixedDepthTree`]; see [`generic::empty_leaf`].
+pub fn empty_leaf() -> Pedersen254Field {
+    generic::empty_leaf::<EdOnBn254>()
+}
+
+/// Derives the nullifier of `identity` for `external_nullifier`; see
+/// [`generic::member_nullifier`].
+pub fn member_nullifier(
+    identity: &Identity,
+    external_nullifier: &Pedersen254Field,
+) -> Pedersen254Field {
+    generic::member_nullifier::<EdOnBn254>(identity, external_nullifier)
+}
@@ -0,0 +1,23 @@
+use super::EdOnBn254;
+use crate::commitments::pedersen::constraint as generic;
+
+/// R1CS representation of the Merkle tree root.
+pub type PedersenRootVar = generic::PedersenRootVar<EdOnBn254>;
+
+pub type PedersenLeafVar = generic::PedersenLeafVar<EdOnBn254>;
+
+/// R1CS representation of the Merkle tree path.
+pub type PedersenPathVar = generic::PedersenPathVar<EdOnBn254>;
+
+pub type MerkleTreeCircuit = generic::MerkleTreeCircuit<EdOnBn254>;
+pub type AnonymousMerkleTreeCircuit = generic::AnonymousMerkleTreeCircuit<EdOnBn254>;
+pub type MultiRootMerkleTreeCircuit = generic::MultiRootMerkleTreeCircuit<EdOnBn254>;
+pub type HistoryMerkleTreeCircuit = generic::HistoryMerkleTreeCircuit<EdOnBn254>;
+pub type BatchMerkleTreeCircuit = generic::BatchMerkleTreeCircuit<EdOnBn254>;
+pub type MemberMerkleTreeCircuit = generic::MemberMerkleTreeCircuit<EdOnBn254>;
+pub type ExpiryMerkleTreeCircuit = generic::ExpiryMerkleTreeCircuit<EdOnBn254>;
+pub type SeniorityMerkleTreeCircuit = generic::SeniorityMerkleTreeCircuit<EdOnBn254>;
+pub type EmailDomainMerkleTreeCircuit = generic::EmailDomainMerkleTreeCircuit<EdOnBn254>;
+pub type RevocationMerkleTreeCircuit = generic::RevocationMerkleTreeCircuit<EdOnBn254>;
+pub type ContextMerkleTreeCircuit = generic::ContextMerkleTreeCircuit<EdOnBn254>;
+pub type NullifierMerkleTreeCircuit = generic::NullifierMerkleTreeCircuit<EdOnBn254>;
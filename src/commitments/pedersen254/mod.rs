@@ -0,0 +1,30 @@
+//! Pedersen backend over ed-on-bn254 (Baby Jubjub), proven with BN254: the
+//! curve-generic [`pedersen`](super::pedersen) backend instantiated with
+//! [`EdOnBn254`]. BN254 is the pairing curve with cheap verification on
+//! Ethereum-compatible chains.
+
+pub mod common; // TODO: module should be private
+pub mod constraint; // TODO: module should be private
+pub mod gm17;
+pub mod groth16;
+
+pub use common::*;
+pub use constraint::*;
+pub use groth16::*;
+
+use super::pedersen::PedersenCurve;
+
+/// The Baby Jubjub curve (ed-on-bn254), whose base field is the scalar field of
+/// [`Bn254`](ark_bn254::Bn254).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBn254;
+
+impl PedersenCurve for EdOnBn254 {
+    type Parameters = ark_ed_on_bn254::EdwardsParameters;
+    type Field = ark_ed_on_bn254::Fq;
+    type Pairing = ark_bn254::Bn254;
+
+    // 1024 bits (128 bytes): the member record header plus ordinary ids and emails
+    const LEAF_NUM_WINDOWS: usize = 256;
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 128;
+}
@@ -0,0 +1,61 @@
+use super::EdOnBls12_377;
+use crate::commitments::pedersen::{common as generic, PedersenCurve};
+use crate::member::Identity;
+use ark_crypto_primitives::CRH;
+
+/////////////////////////////
+
+pub type Pedersen377Field = <EdOnBls12_377 as PedersenCurve>::Field;
+
+/////////////////////////////
+
+pub type TwoToOneWindow = generic::TwoToOneWindow<EdOnBls12_377>;
+pub type TwoToOneHash = generic::TwoToOneHash<EdOnBls12_377>;
+pub type Root = generic::Root<EdOnBls12_377>;
+pub type TwoToOneHashGadget = generic::TwoToOneHashGadget<EdOnBls12_377>;
+
+pub type LeafWindow = generic::LeafWindow<EdOnBls12_377>;
+pub type LeafHash = generic::LeafHash<EdOnBls12_377>;
+pub type Leaf = generic::Leaf<EdOnBls12_377>;
+pub type LeafHashGadget = generic::LeafHashGadget<EdOnBls12_377>;
+
+/// Maximum length of a serialized [`Member`](crate::member::Member) record that
+/// `LeafHash` can absorb.
+pub const MEMBER_RECORD_BYTES: usize = <EdOnBls12_377 as PedersenCurve>::MEMBER_RECORD_BYTES;
+
+pub type LeafHashParamsVar = generic::LeafHashParamsVar<EdOnBls12_377>;
+pub type TwoToOneHashParamsVar = generic::TwoToOneHashParamsVar<EdOnBls12_377>;
+
+/////////////////////////////
+
+pub type MerkleConfig = generic::MerkleConfig<EdOnBls12_377>;
+pub type MerklePath = generic::MerklePath<EdOnBls12_377>;
+pub type MembershipTree = generic::MembershipTree<EdOnBls12_377>;
+pub type MembershipTreeBuilder = generic::MembershipTreeBuilder<EdOnBls12_377>;
+pub type FixedDepthTree = generic::FixedDepthTree<EdOnBls12_377>;
+
+pub type RevocationLeaf = generic::RevocationLeaf<EdOnBls12_377>;
+pub type RevocationTree = generic::RevocationTree<EdOnBls12_377>;
+pub type RootHistory = generic::RootHistory<EdOnBls12_377>;
+
+pub fn new_membership_tree(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as CRH>::Parameters,
+    leaves: &mut Vec<Pedersen377Field>,
+) -> MembershipTree {
+    generic::new_membership_tree::<EdOnBls12_377>(leaf_crh_params, two_to_one_crh_params, leaves)
+}
+
+/// Leaf that pads a [`FixedDepthTree`]; see [`generic::empty_leaf`].
+pub fn empty_leaf() -> Pedersen377Field {
+    generic::empty_leaf::<EdOnBls12_377>()
+}
+
+/// Derives the nullifier of `identity` for `external_nullifier`; see
+/// [`generic::member_nullifier`].
+pub fn member_nullifier(
+    identity: &Identity,
+    external_nullifier: &Pedersen377Field,
+) -> Pedersen377Field {
+    generic::member_nullifier::<EdOnBls12_377>(identity, external_nullifier)
+}
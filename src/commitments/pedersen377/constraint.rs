@@ -0,0 +1,23 @@
+use super::EdOnBls12_377;
+use crate::commitments::pedersen::constraint as generic;
+
+/// R1CS representation of the Merkle tree root.
+pub type PedersenRootVar = generic::PedersenRootVar<EdOnBls12_377>;
+
+pub type PedersenLeafVar = generic::PedersenLeafVar<EdOnBls12_377>;
+
+/// R1CS representation of the Merkle tree path.
+pub type PedersenPathVar = generic::PedersenPathVar<EdOnBls12_377>;
+
+pub type MerkleTreeCircuit = generic::MerkleTreeCircuit<EdOnBls12_377>;
+pub type AnonymousMerkleTreeCircuit = generic::AnonymousMerkleTreeCircuit<EdOnBls12_377>;
+pub type MultiRootMerkleTreeCircuit = generic::MultiRootMerkleTreeCircuit<EdOnBls12_377>;
+pub type HistoryMerkleTreeCircuit = generic::HistoryMerkleTreeCircuit<EdOnBls12_377>;
+pub type BatchMerkleTreeCircuit = generic::BatchMerkleTreeCircuit<EdOnBls12_377>;
+pub type MemberMerkleTreeCircuit = generic::MemberMerkleTreeCircuit<EdOnBls12_377>;
+pub type ExpiryMerkleTreeCircuit = generic::ExpiryMerkleTreeCircuit<EdOnBls12_377>;
+pub type SeniorityMerkleTreeCircuit = generic::SeniorityMerkleTreeCircuit<EdOnBls12_377>;
+pub type EmailDomainMerkleTreeCircuit = generic::EmailDomainMerkleTreeCircuit<EdOnBls12_377>;
+pub type RevocationMerkleTreeCircuit = generic::RevocationMerkleTreeCircuit<EdOnBls12_377>;
+pub type ContextMerkleTreeCircuit = generic::ContextMerkleTreeCircuit<EdOnBls12_377>;
+pub type NullifierMerkleTreeCircuit = generic::NullifierMerkleTreeCircuit<EdOnBls12_377>;
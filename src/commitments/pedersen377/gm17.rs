@@ -0,0 +1,57 @@
+use super::common::{Pedersen377Field, Root};
+use super::constraint::MerkleTreeCircuit;
+use super::groth16::Curve;
+use super::EdOnBls12_377;
+use crate::commitments::pedersen::gm17 as generic;
+use ark_gm17::{Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::SerializationError;
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Generates circuit-specific GM17 keys for the shape of `circuit`.
+pub fn setup<R: RngCore + CryptoRng>(
+    circuit: MerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<(ProvingKey<Curve>, VerifyingKey<Curve>), SynthesisError> {
+    generic::setup::<EdOnBls12_377, R>(circuit, rng)
+}
+
+/// Proves that the circuit's leaf is a member of the tree under its root.
+pub fn prove<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: MerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove`] against the public inputs of
+/// [`MerkleTreeCircuit`].
+pub fn verify(
+    vk: &VerifyingKey<Curve>,
+    root: &Root,
+    leaf_hash: &Pedersen377Field,
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify::<EdOnBls12_377>(vk, root, leaf_hash, proof)
+}
+
+/// Compressed encoding of a GM17 proof.
+pub fn proof_to_bytes(proof: &Proof<Curve>) -> Result<Vec<u8>, SerializationError> {
+    generic::proof_to_bytes::<EdOnBls12_377>(proof)
+}
+
+/// Decodes a proof encoded by [`proof_to_bytes`].
+pub fn proof_from_bytes(bytes: &[u8]) -> Result<Proof<Curve>, SerializationError> {
+    generic::proof_from_bytes::<EdOnBls12_377>(bytes)
+}
+
+/// Compressed encoding of a GM17 verifying key.
+pub fn verifying_key_to_bytes(vk: &VerifyingKey<Curve>) -> Result<Vec<u8>, SerializationError> {
+    generic::verifying_key_to_bytes::<EdOnBls12_377>(vk)
+}
+
+/// Decodes a verifying key encoded by [`verifying_key_to_bytes`].
+pub fn verifying_key_from_bytes(bytes: &[u8]) -> Result<VerifyingKey<Curve>, SerializationError> {
+    generic::verifying_key_from_bytes::<EdOnBls12_377>(bytes)
+}
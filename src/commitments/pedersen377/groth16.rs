@@ -0,0 +1,137 @@
+use super::common::{LeafHash, Pedersen377Field, Root, TwoToOneHash};
+use super::constraint::{
+    AnonymousMerkleTreeCircuit, ContextMerkleTreeCircuit, HistoryMerkleTreeCircuit,
+    MultiRootMerkleTreeCircuit, NullifierMerkleTreeCircuit,
+};
+use super::EdOnBls12_377;
+use crate::commitments::pedersen::groth16 as generic;
+use crate::nullifier::NullifierStore;
+use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+use ark_groth16::{Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Pairing curve whose scalar field matches the circuit field of this backend.
+pub type Curve = ark_bls12_377::Bls12_377;
+
+/// Sets up keys that prove membership in a
+/// [`FixedDepthTree`](super::common::FixedDepthTree) of `depth` at every member
+/// count; see [`generic::setup_for_depth`].
+pub fn setup_for_depth<R: RngCore + CryptoRng>(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as TwoToOneCRH>::Parameters,
+    depth: usize,
+    rng: &mut R,
+) -> Result<(ProvingKey<Curve>, VerifyingKey<Curve>), SynthesisError> {
+    generic::setup_for_depth::<EdOnBls12_377, R>(leaf_crh_params, two_to_one_crh_params, depth, rng)
+}
+
+/// Proves membership without revealing which leaf belongs to the prover.
+pub fn prove_anonymous<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: AnonymousMerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove_anonymous::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_anonymous`] against `root`, the only
+/// public input of [`AnonymousMerkleTreeCircuit`].
+pub fn verify_anonymous(
+    vk: &VerifyingKey<Curve>,
+    root: &Root,
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify_anonymous::<EdOnBls12_377>(vk, root, proof)
+}
+
+/// Proves membership under any root of a [`super::common::RootHistory`], so the
+/// proof survives registrations that happen while it is being generated.
+pub fn prove_with_history<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: HistoryMerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove_with_history::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_history`] against the current
+/// commitment of the root history.
+pub fn verify_with_history(
+    vk: &VerifyingKey<Curve>,
+    history_root: &Root,
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify_with_history::<EdOnBls12_377>(vk, history_root, proof)
+}
+
+/// Proves membership in one of the circuit's roots without revealing which.
+pub fn prove_any_root<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: MultiRootMerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove_any_root::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_any_root`]. `roots` must be given in
+/// the same order, and have the same length, as at setup time.
+pub fn verify_any_root(
+    vk: &VerifyingKey<Curve>,
+    roots: &[Root],
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify_any_root::<EdOnBls12_377>(vk, roots, proof)
+}
+
+/// Proves membership bound to the circuit's context, e.g. a message hash or a
+/// verifier nonce (see [`crate::context::message_context`]).
+pub fn prove_with_context<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: ContextMerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove_with_context::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_context`]; it only verifies under
+/// the context it was created for.
+pub fn verify_with_context(
+    vk: &VerifyingKey<Curve>,
+    root: &Root,
+    context: &Pedersen377Field,
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify_with_context::<EdOnBls12_377>(vk, root, context, proof)
+}
+
+/// Proves membership and derives the member's nullifier for the circuit's
+/// external nullifier.
+pub fn prove_with_nullifier<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<Curve>,
+    circuit: NullifierMerkleTreeCircuit,
+    rng: &mut R,
+) -> Result<Proof<Curve>, SynthesisError> {
+    generic::prove_with_nullifier::<EdOnBls12_377, R>(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_with_nullifier`] and records its
+/// nullifier in `store`. A nullifier that was already accepted for
+/// `external_nullifier` is rejected.
+pub fn verify_with_nullifier(
+    vk: &VerifyingKey<Curve>,
+    store: &mut NullifierStore<Pedersen377Field>,
+    root: &Root,
+    external_nullifier: &Pedersen377Field,
+    nullifier: &Pedersen377Field,
+    proof: &Proof<Curve>,
+) -> Result<bool, SynthesisError> {
+    generic::verify_with_nullifier::<EdOnBls12_377>(
+        vk,
+        store,
+        root,
+        external_nullifier,
+        nullifier,
+        proof,
+    )
+}
@@ -0,0 +1,31 @@
+//! Pedersen backend over ed-on-bls12-377, proven with BLS12-377: the curve-generic
+//! [`pedersen`](super::pedersen) backend instantiated with [`EdOnBls12_377`].
+//! BLS12-377 is the inner curve of the BLS12-377/BW6-761 two-chain, so these
+//! proofs can be verified inside BW6-761 circuits; see [`recursion`].
+
+pub mod common; // TODO: module should be private
+pub mod constraint; // TODO: module should be private
+pub mod gm17;
+pub mod groth16;
+pub mod recursion;
+
+pub use common::*;
+pub use constraint::*;
+pub use groth16::*;
+
+use super::pedersen::PedersenCurve;
+
+/// The ed-on-bls12-377 curve, whose base field is the scalar field of
+/// [`Bls12_377`](ark_bls12_377::Bls12_377).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBls12_377;
+
+impl PedersenCurve for EdOnBls12_377 {
+    type Parameters = ark_ed_on_bls12_377::EdwardsParameters;
+    type Field = ark_ed_on_bls12_377::Fq;
+    type Pairing = ark_bls12_377::Bls12_377;
+
+    // 1024 bits (128 bytes): the member record header plus ordinary ids and emails
+    const LEAF_NUM_WINDOWS: usize = 256;
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 128;
+}
@@ -0,0 +1,255 @@
+use super::common::{Pedersen377Field, Root};
+use super::groth16::Curve;
+use ark_bls12_377::constraints::PairingVar;
+use ark_crypto_primitives::snark::{BooleanInputVar, SNARKGadget};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::constraints::{Groth16VerifierGadget, ProofVar, VerifyingKeyVar};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::{eq::EqGadget, prelude::Boolean};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Pairing curve of the outer proof. Its scalar field is the base field of
+/// [`Curve`], so BLS12-377 pairings can be checked natively in its circuits.
+pub type OuterCurve = ark_bw6_761::BW6_761;
+
+/// Circuit field of [`RecursiveMembershipCircuit`].
+pub type OuterField = ark_bw6_761::Fr;
+
+type InnerVerifierGadget = Groth16VerifierGadget<Curve, PairingVar>;
+
+/// Public statement of an inner [`super::MerkleTreeCircuit`] proof: the root and
+/// the member's leaf hash, in the order they are allocated by that circuit.
+pub type MembershipStatement = (Root, Pedersen377Field);
+
+/// Outer circuit that verifies BLS12-377 Groth16 membership proofs inside
+/// BW6-761, so that many of them are folded into one outer proof.
+///
+/// Every inner proof must come from the key pair behind `inner_vk`. The inner
+/// statements are the public inputs of the outer circuit; see
+/// [`outer_public_inputs`] for how they are encoded.
+#[derive(Clone)]
+pub struct RecursiveMembershipCircuit {
+    // constant that will be embedded into the circuit
+    pub inner_vk: VerifyingKey<Curve>,
+
+    // These are the public inputs to the circuit
+    pub statements: Vec<MembershipStatement>,
+
+    // These are the private witnesses to the circuit, one per statement
+    pub proofs: Vec<Option<Proof<Curve>>>,
+}
+
+impl ConstraintSynthesizer<OuterField> for RecursiveMembershipCircuit {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<OuterField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        if self.statements.len() != self.proofs.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // Allocate the inner verifying key as a constant
+        let inner_vk = VerifyingKeyVar::<Curve, PairingVar>::new_constant(
+            ark_relations::ns!(cs, "inner_vk"),
+            &self.inner_vk,
+        )?;
+        let inner_pvk = inner_vk.prepare()?;
+
+        for ((root, leaf_hash), proof) in self.statements.iter().zip(self.proofs) {
+            // Allocate the inner statement as public input
+            let inputs = BooleanInputVar::<Pedersen377Field, OuterField>::new_input(
+                ark_relations::ns!(cs, "inner_inputs"),
+                || Ok(vec![*root, *leaf_hash]),
+            )?;
+
+            // Allocate the inner proof as witness. The allocation reads the proof
+            // even during setup, where a placeholder stands in for it.
+            let proof = ProofVar::<Curve, PairingVar>::new_witness(
+                ark_relations::ns!(cs, "inner_proof"),
+                || Ok(proof.unwrap_or_default()),
+            )?;
+
+            let is_valid =
+                InnerVerifierGadget::verify_with_processed_vk(&inner_pvk, &inputs, &proof)?;
+            is_valid.enforce_equal(&Boolean::TRUE)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encodes inner statements as public inputs of [`RecursiveMembershipCircuit`].
+///
+/// The inner field elements are concatenated as big-endian bits and repacked
+/// into as few outer field elements as fit, exactly as the verifier gadget's
+/// [`BooleanInputVar`] allocates them.
+pub fn outer_public_inputs(statements: &[MembershipStatement]) -> Vec<OuterField> {
+    let inner_bits = Pedersen377Field::size_in_bits();
+    // The outer field is larger than the inner one, so one bit less than its
+    // size always fits
+    let capacity = OuterField::size_in_bits() - 1;
+
+    statements
+        .iter()
+        .flat_map(|(root, leaf_hash)| {
+            let mut bits = Vec::with_capacity(2 * inner_bits);
+            for input in [root, leaf_hash] {
+                let mut input_bits = input.into_repr().to_bits_le();
+                input_bits.resize(inner_bits, false);
+                input_bits.reverse();
+                bits.append(&mut input_bits);
+            }
+
+            bits.chunks(capacity)
+                .map(|chunk| {
+                    OuterField::from_repr(<OuterField as PrimeField>::BigInt::from_bits_be(chunk))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Folds the circuit's inner membership proofs into one BW6-761 proof.
+pub fn prove_recursive<R: RngCore + CryptoRng>(
+    pk: &ProvingKey<OuterCurve>,
+    circuit: RecursiveMembershipCircuit,
+    rng: &mut R,
+) -> Result<Proof<OuterCurve>, SynthesisError> {
+    Groth16::<OuterCurve>::prove(pk, circuit, rng)
+}
+
+/// Verifies a proof produced by [`prove_recursive`]. `statements` must be given
+/// in the same order, and have the same length, as at setup time.
+pub fn verify_recursive(
+    vk: &VerifyingKey<OuterCurve>,
+    statements: &[MembershipStatement],
+    proof: &Proof<OuterCurve>,
+) -> Result<bool, SynthesisError> {
+    Groth16::<OuterCurve>::verify(vk, &outer_public_inputs(statements), proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commitments::pedersen377::{
+            common::{new_membership_tree, LeafHash, TwoToOneHash},
+            constraint::MerkleTreeCircuit,
+        },
+        member::Member,
+    };
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    /// Proves membership of every member with one inner key pair.
+    fn inner_proofs() -> (
+        VerifyingKey<Curve>,
+        Vec<MembershipStatement>,
+        Vec<Proof<Curve>>,
+    ) {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        let circuit_for = |index: usize| MerkleTreeCircuit {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params: two_to_one_crh_params.clone(),
+            root: tree.root(),
+            leaf_hash: leaves[index],
+            authentication_path: Some(tree.generate_proof(index).unwrap()),
+        };
+
+        let (pk, vk) = Groth16::<Curve>::circuit_specific_setup(circuit_for(0), &mut rng).unwrap();
+        let proofs = (0..members.len())
+            .map(|index| Groth16::<Curve>::prove(&pk, circuit_for(index), &mut rng).unwrap())
+            .collect();
+        let statements = (0..members.len())
+            .map(|index| (tree.root(), leaves[index]))
+            .collect();
+
+        (vk, statements, proofs)
+    }
+
+    fn is_satisfied(circuit: RecursiveMembershipCircuit) -> bool {
+        let cs = ConstraintSystem::<OuterField>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn folds_several_inner_proofs() {
+        let (inner_vk, statements, proofs) = inner_proofs();
+
+        let circuit = RecursiveMembershipCircuit {
+            inner_vk,
+            statements,
+            proofs: proofs.into_iter().map(Some).collect(),
+        };
+        assert!(is_satisfied(circuit));
+    }
+
+    #[test]
+    fn rejects_inner_proof_for_other_statement() {
+        let (inner_vk, mut statements, proofs) = inner_proofs();
+
+        // Bob's proof does not attest to Alice's leaf
+        statements[1].1 = statements[0].1;
+        let circuit = RecursiveMembershipCircuit {
+            inner_vk,
+            statements,
+            proofs: proofs.into_iter().map(Some).collect(),
+        };
+        assert!(!is_satisfied(circuit));
+    }
+
+    #[test]
+    fn public_inputs_match_circuit_encoding() {
+        let (inner_vk, statements, proofs) = inner_proofs();
+
+        let cs = ConstraintSystem::<OuterField>::new_ref();
+        let circuit = RecursiveMembershipCircuit {
+            inner_vk,
+            statements: statements.clone(),
+            proofs: proofs.into_iter().map(Some).collect(),
+        };
+        circuit.generate_constraints(cs.clone()).unwrap();
+
+        // The first instance variable is the constant one
+        let instance = cs.borrow().unwrap().instance_assignment.clone();
+        assert_eq!(instance[1..], outer_public_inputs(&statements)[..]);
+    }
+
+    #[test]
+    fn recursive_groth16_snark() {
+        let mut rng = ark_std::test_rng();
+        let (inner_vk, statements, proofs) = inner_proofs();
+
+        let circuit = RecursiveMembershipCircuit {
+            inner_vk,
+            statements: statements[..1].to_vec(),
+            proofs: vec![Some(proofs[0].clone())],
+        };
+
+        let (pk, vk) =
+            Groth16::<OuterCurve>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        let proof = prove_recursive(&pk, circuit, &mut rng).unwrap();
+
+        assert!(verify_recursive(&vk, &statements[..1], &proof).unwrap());
+        assert!(!verify_recursive(&vk, &statements[1..], &proof).unwrap());
+    }
+}
@@ -1,112 +1,68 @@
-use crate::member::Member;
-use ark_crypto_primitives::crh::constraints::{CRHGadget, TwoToOneCRHGadget};
-use ark_crypto_primitives::crh::injective_map::constraints::{
-    PedersenCRHCompressorGadget, TECompressorGadget,
-};
-use ark_crypto_primitives::crh::TwoToOneCRH;
-use ark_crypto_primitives::crh::{
-    injective_map::{PedersenCRHCompressor, TECompressor},
-    pedersen,
-};
-use ark_crypto_primitives::merkle_tree::Config;
-use ark_crypto_primitives::{MerkleTree, Path, CRH};
-use ark_ed_on_bls12_381::{constraints::EdwardsVar, EdwardsProjective};
+use super::EdOnBls12_381;
+use crate::commitments::pedersen::{common as generic, PedersenCurve};
+use crate::member::Identity;
+use ark_crypto_primitives::CRH;
 
 /////////////////////////////
 
-pub type Pedersen381Field = ark_ed_on_bls12_381::Fq;
+pub type Pedersen381Field = <EdOnBls12_381 as PedersenCurve>::Field;
 
 /////////////////////////////
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct TwoToOneWindow;
-impl pedersen::Window for TwoToOneWindow {
-    const WINDOW_SIZE: usize = 4;
-    const NUM_WINDOWS: usize = 128;
-}
-
-pub type TwoToOneHash = PedersenCRHCompressor<EdwardsProjective, TECompressor, TwoToOneWindow>;
-pub type Root = <TwoToOneHash as TwoToOneCRH>::Output;
-
-pub type TwoToOneHashGadget = PedersenCRHCompressorGadget<
-    EdwardsProjective,
-    TECompressor,
-    TwoToOneWindow,
-    EdwardsVar,
-    TECompressorGadget,
->;
-
-/////////////////////////////
+pub type TwoToOneWindow = generic::TwoToOneWindow<EdOnBls12_381>;
+pub type TwoToOneHash = generic::TwoToOneHash<EdOnBls12_381>;
+pub type Root = generic::Root<EdOnBls12_381>;
+pub type TwoToOneHashGadget = generic::TwoToOneHashGadget<EdOnBls12_381>;
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct LeafWindow;
-impl pedersen::Window for LeafWindow {
-    const WINDOW_SIZE: usize = 4;
-    const NUM_WINDOWS: usize = 144;
-}
+pub type LeafWindow = generic::LeafWindow<EdOnBls12_381>;
+pub type LeafHash = generic::LeafHash<EdOnBls12_381>;
+pub type Leaf = generic::Leaf<EdOnBls12_381>;
+pub type LeafHashGadget = generic::LeafHashGadget<EdOnBls12_381>;
 
-pub type LeafHash = PedersenCRHCompressor<EdwardsProjective, TECompressor, LeafWindow>;
-pub type Leaf = <LeafHash as CRH>::Output;
+/// Maximum length of a serialized [`Member`](crate::member::Member) record that
+/// `LeafHash` can absorb.
+pub const MEMBER_RECORD_BYTES: usize = <EdOnBls12_381 as PedersenCurve>::MEMBER_RECORD_BYTES;
 
-pub type LeafHashGadget = PedersenCRHCompressorGadget<
-    EdwardsProjective,
-    TECompressor,
-    LeafWindow,
-    EdwardsVar,
-    TECompressorGadget,
->;
+pub type LeafHashParamsVar = generic::LeafHashParamsVar<EdOnBls12_381>;
+pub type TwoToOneHashParamsVar = generic::TwoToOneHashParamsVar<EdOnBls12_381>;
 
 /////////////////////////////
 
-pub type LeafHashParamsVar =
-    <LeafHashGadget as CRHGadget<LeafHash, Pedersen381Field>>::ParametersVar;
-
-pub type TwoToOneHashParamsVar =
-    <TwoToOneHashGadget as TwoToOneCRHGadget<TwoToOneHash, Pedersen381Field>>::ParametersVar;
+pub type MerkleConfig = generic::MerkleConfig<EdOnBls12_381>;
+pub type MerklePath = generic::MerklePath<EdOnBls12_381>;
+pub type MembershipTree = generic::MembershipTree<EdOnBls12_381>;
+pub type MembershipTreeBuilder = generic::MembershipTreeBuilder<EdOnBls12_381>;
+pub type FixedDepthTree = generic::FixedDepthTree<EdOnBls12_381>;
 
-/////////////////////////////
-
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
-pub struct MerkleConfig;
-impl Config for MerkleConfig {
-    type LeafHash = LeafHash;
-    type TwoToOneHash = TwoToOneHash;
-}
-
-pub type MerklePath = Path<MerkleConfig>;
-
-pub type MembershipTree = MerkleTree<MerkleConfig>;
+pub type RevocationLeaf = generic::RevocationLeaf<EdOnBls12_381>;
+pub type RevocationTree = generic::RevocationTree<EdOnBls12_381>;
+pub type RootHistory = generic::RootHistory<EdOnBls12_381>;
 
 pub fn new_membership_tree(
     leaf_crh_params: &<LeafHash as CRH>::Parameters,
     two_to_one_crh_params: &<TwoToOneHash as CRH>::Parameters,
     leaves: &mut Vec<Pedersen381Field>,
 ) -> MembershipTree {
-    clean_membership_list(leaf_crh_params, leaves);
-    MembershipTree::new(leaf_crh_params, two_to_one_crh_params, leaves.as_ref()).unwrap()
+    generic::new_membership_tree::<EdOnBls12_381>(leaf_crh_params, two_to_one_crh_params, leaves)
 }
 
-fn clean_membership_list(
-    leaf_crh_params: &<LeafHash as CRH>::Parameters,
-    leaves: &mut Vec<Pedersen381Field>,
-) {
-    let leaf_crh_params: &<LeafHash as CRH>::Parameters = &leaf_crh_params;
-    let num_members = leaves.len();
-
-    let num_needed = if num_members == 1 {
-        1
-    } else {
-        num_members.next_power_of_two() - num_members
-    };
+/// Leaf that pads a [`FixedDepthTree`]; see [`generic::empty_leaf`].
+pub fn empty_leaf() -> Pedersen381Field {
+    generic::empty_leaf::<EdOnBls12_381>()
+}
+
+/// Derives the nullifier of `identity` for `external_nullifier`; see
+/// [`generic::member_nullifier`].
+pub fn member_nullifier(
+    identity: &Identity,
+    external_nullifier: &Pedersen381Field,
+) -> Pedersen381Field {
+    generic::member_nullifier::<EdOnBls12_381>(identity, external_nullifier)
+}
 
-    leaves.append(&mut vec![
-        <LeafHash as CRH>::evaluate(
-            leaf_crh_params,
-            Member::default().to_bytes().as_slice(),
-        )
-        .unwrap();
-        num_needed
-    ]);
+#[cfg(test)]
+fn clean_membership_list(leaves: &mut Vec<Pedersen381Field>) {
+    generic::clean_membership_list::<EdOnBls12_381>(leaves)
 }
 
 #[cfg(test)]
@@ -134,13 +90,15 @@ mod membership_tree_tests {
     fn one_leaf() {
         let params = setup().0;
 
-        let members = &vec![Member::default()];
+        let members = &[Member::default()];
         let mut leaves = members
             .iter()
-            .map(|member| <LeafHash as CRH>::evaluate(&params, &member.to_bytes()).unwrap())
+            .map(|member| {
+                <LeafHash as CRH>::evaluate(&params, &member.to_bytes().unwrap()).unwrap()
+            })
             .collect::<Vec<_>>();
 
-        clean_membership_list(&params, &mut leaves);
+        clean_membership_list(&mut leaves);
         assert!(leaves.len().next_power_of_two() == 2)
     }
 
@@ -148,13 +106,15 @@ mod membership_tree_tests {
     fn two_leaves() {
         let params = setup().0;
 
-        let members = vec![Member::default(), Member::default()];
+        let members = [Member::default(), Member::default()];
         let mut leaves = members
             .iter()
-            .map(|member| <LeafHash as CRH>::evaluate(&params, &member.to_bytes()).unwrap())
+            .map(|member| {
+                <LeafHash as CRH>::evaluate(&params, &member.to_bytes().unwrap()).unwrap()
+            })
             .collect::<Vec<_>>();
 
-        clean_membership_list(&params, &mut leaves);
+        clean_membership_list(&mut leaves);
         assert_eq!(leaves.len(), 2); // Already a power of two, no additional elements should be added
     }
 
@@ -162,13 +122,15 @@ mod membership_tree_tests {
     fn three_leaves() {
         let params = setup().0;
 
-        let members = vec![Member::default(), Member::default(), Member::default()];
+        let members = [Member::default(), Member::default(), Member::default()];
         let mut leaves = members
             .iter()
-            .map(|member| <LeafHash as CRH>::evaluate(&params, &member.to_bytes()).unwrap())
+            .map(|member| {
+                <LeafHash as CRH>::evaluate(&params, &member.to_bytes().unwrap()).unwrap()
+            })
             .collect::<Vec<_>>();
 
-        clean_membership_list(&params, &mut leaves);
+        clean_membership_list(&mut leaves);
         assert_eq!(leaves.len(), 4); // Should add 1 more element to make it a power of two
     }
 
@@ -183,7 +145,7 @@ mod membership_tree_tests {
             Member::new("2".into(), "2@usc.edu".into(), None),
         ];
         let leaves = members.clone().map(|member| {
-            <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes()).unwrap()
+            <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes().unwrap()).unwrap()
         });
 
         let tree: MerkleTree<MerkleConfig> = new_membership_tree(
@@ -201,7 +163,8 @@ mod membership_tree_tests {
                 &leaf_crh_params,
                 &two_to_one_crh_params,
                 &root,
-                &<LeafHash as CRH>::evaluate(&leaf_crh_params, &members[1].to_bytes()).unwrap(), // The claimed leaf
+                &<LeafHash as CRH>::evaluate(&leaf_crh_params, &members[1].to_bytes().unwrap())
+                    .unwrap(), // The claimed leaf
             )
             .unwrap();
 
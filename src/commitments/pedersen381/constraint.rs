@@ -1,76 +1,26 @@
-use super::common::{
-    LeafHash, LeafHashGadget, LeafHashParamsVar, MerkleConfig, MerklePath, Pedersen381Field, Root,
-    TwoToOneHash, TwoToOneHashGadget, TwoToOneHashParamsVar,
-};
-use ark_crypto_primitives::{
-    crh::{CRHGadget, TwoToOneCRH, TwoToOneCRHGadget},
-    PathVar, CRH,
-};
-use ark_r1cs_std::alloc::AllocVar;
-use ark_r1cs_std::{eq::EqGadget, prelude::Boolean};
-use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
+use super::EdOnBls12_381;
+use crate::commitments::pedersen::constraint as generic;
 
 /// R1CS representation of the Merkle tree root.
-pub type PedersenRootVar =
-    <TwoToOneHashGadget as TwoToOneCRHGadget<TwoToOneHash, Pedersen381Field>>::OutputVar;
+pub type PedersenRootVar = generic::PedersenRootVar<EdOnBls12_381>;
 
-pub type PedersenLeafVar = <LeafHashGadget as CRHGadget<LeafHash, Pedersen381Field>>::OutputVar;
+pub type PedersenLeafVar = generic::PedersenLeafVar<EdOnBls12_381>;
 
 /// R1CS representation of the Merkle tree path.
-pub type PedersenPathVar =
-    PathVar<MerkleConfig, LeafHashGadget, TwoToOneHashGadget, Pedersen381Field>;
-
-#[derive(Clone)]
-pub struct MerkleTreeCircuit {
-    // constants that will be embedded into the circuit
-    pub leaf_crh_params: <LeafHash as CRH>::Parameters,
-    pub two_to_one_crh_params: <TwoToOneHash as TwoToOneCRH>::Parameters,
-
-    // These are the public inputs to the circuit
-    pub root: Root,
-    pub leaf_hash: Pedersen381Field,
-
-    // This is the private witness to the circuit
-    pub authentication_path: Option<MerklePath>,
-}
-
-impl ConstraintSynthesizer<Pedersen381Field> for MerkleTreeCircuit {
-    fn generate_constraints(
-        self,
-        cs: ark_relations::r1cs::ConstraintSystemRef<Pedersen381Field>,
-    ) -> ark_relations::r1cs::Result<()> {
-        // Allocate parameters as constants
-        let leaf_crh_params = LeafHashParamsVar::new_constant(cs.clone(), self.leaf_crh_params)?;
-        let two_to_one_crh_params =
-            TwoToOneHashParamsVar::new_constant(cs.clone(), self.two_to_one_crh_params)?;
-
-        // Allocate public inputs
-        let root =
-            PedersenRootVar::new_input(ark_relations::ns!(cs, "root_var"), || Ok(&self.root))?;
-
-        let hashed_leaf: PedersenLeafVar =
-            PedersenLeafVar::new_input(ark_relations::ns!(cs, "leaf_var"), || Ok(&self.leaf_hash))?;
-
-        // Allocate path as witness
-        let path: PedersenPathVar =
-            PedersenPathVar::new_witness(ark_relations::ns!(cs, "path_witness"), || {
-                self.authentication_path
-                    .as_ref()
-                    .ok_or(SynthesisError::AssignmentMissing)
-            })?;
-
-        let is_member: Boolean<Pedersen381Field> = path.verify_membership(
-            &leaf_crh_params,
-            &two_to_one_crh_params,
-            &root,
-            &hashed_leaf,
-        )?;
-
-        is_member.enforce_equal(&Boolean::TRUE)?;
-
-        Ok(())
-    }
-}
+pub type PedersenPathVar = generic::PedersenPathVar<EdOnBls12_381>;
+
+pub type MerkleTreeCircuit = generic::MerkleTreeCircuit<EdOnBls12_381>;
+pub type AnonymousMerkleTreeCircuit = generic::AnonymousMerkleTreeCircuit<EdOnBls12_381>;
+pub type MultiRootMerkleTreeCircuit = generic::MultiRootMerkleTreeCircuit<EdOnBls12_381>;
+pub type HistoryMerkleTreeCircuit = generic::HistoryMerkleTreeCircuit<EdOnBls12_381>;
+pub type BatchMerkleTreeCircuit = generic::BatchMerkleTreeCircuit<EdOnBls12_381>;
+pub type MemberMerkleTreeCircuit = generic::MemberMerkleTreeCircuit<EdOnBls12_381>;
+pub type ExpiryMerkleTreeCircuit = generic::ExpiryMerkleTreeCircuit<EdOnBls12_381>;
+pub type SeniorityMerkleTreeCircuit = generic::SeniorityMerkleTreeCircuit<EdOnBls12_381>;
+pub type EmailDomainMerkleTreeCircuit = generic::EmailDomainMerkleTreeCircuit<EdOnBls12_381>;
+pub type RevocationMerkleTreeCircuit = generic::RevocationMerkleTreeCircuit<EdOnBls12_381>;
+pub type ContextMerkleTreeCircuit = generic::ContextMerkleTreeCircuit<EdOnBls12_381>;
+pub type NullifierMerkleTreeCircuit = generic::NullifierMerkleTreeCircuit<EdOnBls12_381>;
 
 #[cfg(test)]
 mod tests {
@@ -107,7 +57,7 @@ mod tests {
 
         let leaves = members
             .clone()
-            .map(|member| member.hash::<LeafHash>(&leaf_crh_params));
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap());
 
         let tree: MerkleTree<MerkleConfig> =
             MerkleTree::new(&leaf_crh_params, &two_to_one_crh_params, &leaves).unwrap();
@@ -125,7 +75,7 @@ mod tests {
 
             // public inputs
             root,
-            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params),
+            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params).unwrap(),
 
             // witness
             authentication_path: Some(path),
@@ -189,12 +139,12 @@ mod tests {
             Member::new("8".into(), "8@usc.edu".into(), None),
         ];
 
-        let org1_leaves = organization1
-            .clone()
-            .map(|m| <LeafHash as CRH>::evaluate(&leaf_crh_params, &m.to_bytes()).unwrap());
-        let org2_leaves = organization2
-            .clone()
-            .map(|m| <LeafHash as CRH>::evaluate(&leaf_crh_params, &m.to_bytes()).unwrap());
+        let org1_leaves = organization1.clone().map(|m| {
+            <LeafHash as CRH>::evaluate(&leaf_crh_params, &m.to_bytes().unwrap()).unwrap()
+        });
+        let org2_leaves = organization2.clone().map(|m| {
+            <LeafHash as CRH>::evaluate(&leaf_crh_params, &m.to_bytes().unwrap()).unwrap()
+        });
         let tree =
             MembershipTree::new(&leaf_crh_params, &two_to_one_crh_params, &org1_leaves).unwrap();
 
@@ -215,7 +165,8 @@ mod tests {
             // public inputs
             root: wrong_root,
             leaf_hash: Member::new("5".into(), "5@usc.edu".into(), None)
-                .hash::<LeafHash>(&leaf_crh_params),
+                .hash::<LeafHash>(&leaf_crh_params)
+                .unwrap(),
 
             // witness
             authentication_path: Some(proof),
@@ -1,8 +1,31 @@
+//! Pedersen backend over ed-on-bls12-381, proven with BLS12-381: the curve-generic
+//! [`pedersen`](super::pedersen) backend instantiated with [`EdOnBls12_381`].
+
 pub mod common; // TODO: module should be private
 pub mod constraint; // TODO: module should be private
+pub mod gm17;
+pub mod groth16;
 
 pub use common::*;
 pub use constraint::*;
+pub use groth16::*;
+
+use super::pedersen::PedersenCurve;
+
+/// The ed-on-bls12-381 curve, whose base field is the scalar field of
+/// [`Bls12_381`](ark_bls12_381::Bls12_381).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBls12_381;
+
+impl PedersenCurve for EdOnBls12_381 {
+    type Parameters = ark_ed_on_bls12_381::EdwardsParameters;
+    type Field = ark_ed_on_bls12_381::Fq;
+    type Pairing = ark_bls12_381::Bls12_381;
+
+    // 1024 bits (128 bytes): the member record header plus ordinary ids and emails
+    const LEAF_NUM_WINDOWS: usize = 256;
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 128;
+}
 
 #[cfg(test)]
 mod groth16_tests {
@@ -25,7 +48,7 @@ mod groth16_tests {
         let two_to_one_crh_params = <TwoToOneHash as CRH>::setup(&mut rng).unwrap();
 
         // Create some test members
-        let members = vec![
+        let members = [
             Member::new("alice".into(), "alice@usc.edu".into(), None),
             Member::new("bob".into(), "bob@usc.edu".into(), None),
             Member::new("carol".into(), "carol@usc.edu".into(), None),
@@ -35,7 +58,7 @@ mod groth16_tests {
         let mut leaves = members
             .iter()
             .map(|member| {
-                <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes()).unwrap()
+                <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes().unwrap()).unwrap()
             })
             .collect::<Vec<_>>();
 
@@ -50,9 +73,9 @@ mod groth16_tests {
         // Create circuit
         let circuit = MerkleTreeCircuit {
             leaf_crh_params: leaf_crh_params.clone(),
-            two_to_one_crh_params: two_to_one_crh_params,
+            two_to_one_crh_params,
             root,
-            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params),
+            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params).unwrap(),
             authentication_path: Some(merkle_path),
         };
 
@@ -64,7 +87,8 @@ mod groth16_tests {
         let proof = Groth16::<Bls12_381>::prove(&pk, circuit.clone(), &mut rng).unwrap();
 
         // Calculate public inputs
-        let public_inputs: Vec<_> = vec![root, members[1].hash::<LeafHash>(&leaf_crh_params)];
+        let public_inputs: Vec<_> =
+            vec![root, members[1].hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
         // Verify the proof
         let verified =
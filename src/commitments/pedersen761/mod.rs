@@ -1,8 +1,31 @@
-pub mod common;
-pub mod constraint;
+//! Pedersen backend over ed-on-bw6-761, proven with BW6-761: the curve-generic
+//! [`pedersen`](super::pedersen) backend instantiated with [`EdOnBw6_761`].
+
+pub mod common; // TODO: module should be private
+pub mod constraint; // TODO: module should be private
+pub mod gm17;
+pub mod groth16;
 
 pub use common::*;
 pub use constraint::*;
+pub use groth16::*;
+
+use super::pedersen::PedersenCurve;
+
+/// The ed-on-bw6-761 curve, whose base field is the scalar field of
+/// [`BW6_761`](ark_bw6_761::BW6_761).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdOnBw6_761;
+
+impl PedersenCurve for EdOnBw6_761 {
+    type Parameters = ark_ed_on_bw6_761::EdwardsParameters;
+    type Field = ark_ed_on_bw6_761::Fq;
+    type Pairing = ark_bw6_761::BW6_761;
+
+    // 1024 bits (128 bytes): the member record header plus ordinary ids and emails
+    const LEAF_NUM_WINDOWS: usize = 256;
+    const TWO_TO_ONE_NUM_WINDOWS: usize = 192;
+}
 
 #[cfg(test)]
 mod groth16_tests {
@@ -23,7 +46,7 @@ mod groth16_tests {
         let two_to_one_crh_params = <TwoToOneHash as CRH>::setup(&mut rng).unwrap();
 
         // Create some test members
-        let members = vec![
+        let members = [
             Member::new("alice".into(), "alice@usc.edu".into(), None),
             Member::new("bob".into(), "bob@usc.edu".into(), None),
             Member::new("carol".into(), "carol@usc.edu".into(), None),
@@ -33,7 +56,7 @@ mod groth16_tests {
         let mut leaves = members
             .iter()
             .map(|member| {
-                <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes()).unwrap()
+                <LeafHash as CRH>::evaluate(&leaf_crh_params, &member.to_bytes().unwrap()).unwrap()
             })
             .collect::<Vec<_>>();
 
@@ -48,9 +71,9 @@ mod groth16_tests {
         // Create circuit
         let circuit = MerkleTreeCircuit {
             leaf_crh_params: leaf_crh_params.clone(),
-            two_to_one_crh_params: two_to_one_crh_params,
+            two_to_one_crh_params,
             root,
-            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params),
+            leaf_hash: members[1].hash::<LeafHash>(&leaf_crh_params).unwrap(),
             authentication_path: Some(merkle_path),
         };
 
@@ -62,7 +85,7 @@ mod groth16_tests {
         let proof = Groth16::<BW6_761>::prove(&pk, circuit.clone(), &mut rng).unwrap();
 
         // Calculate public inputs
-        let public_inputs = vec![root, members[1].hash::<LeafHash>(&leaf_crh_params)];
+        let public_inputs = vec![root, members[1].hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
         // Verify the proof
         let verified =
@@ -0,0 +1,123 @@
+use crate::commitments::empty_leaf;
+use crate::crh::poseidon::{constraints::PoseidonCRHGadget, PoseidonCRH};
+use ark_crypto_primitives::crh::constraints::{CRHGadget, TwoToOneCRHGadget};
+use ark_crypto_primitives::crh::TwoToOneCRH;
+use ark_crypto_primitives::merkle_tree::Config;
+use ark_crypto_primitives::{MerkleTree, Path, CRH};
+
+/////////////////////////////
+
+pub type Poseidon381Field = ark_bls12_381::Fr;
+
+/////////////////////////////
+
+pub type TwoToOneHash = PoseidonCRH<Poseidon381Field>;
+pub type Root = <TwoToOneHash as TwoToOneCRH>::Output;
+
+pub type TwoToOneHashGadget = PoseidonCRHGadget<Poseidon381Field>;
+
+/////////////////////////////
+
+pub type LeafHash = PoseidonCRH<Poseidon381Field>;
+pub type Leaf = <LeafHash as CRH>::Output;
+
+pub type LeafHashGadget = PoseidonCRHGadget<Poseidon381Field>;
+
+/////////////////////////////
+
+pub type LeafHashParamsVar =
+    <LeafHashGadget as CRHGadget<LeafHash, Poseidon381Field>>::ParametersVar;
+
+pub type TwoToOneHashParamsVar =
+    <TwoToOneHashGadget as TwoToOneCRHGadget<TwoToOneHash, Poseidon381Field>>::ParametersVar;
+
+/////////////////////////////
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct MerkleConfig;
+impl Config for MerkleConfig {
+    type LeafHash = LeafHash;
+    type TwoToOneHash = TwoToOneHash;
+}
+
+pub type MerklePath = Path<MerkleConfig>;
+
+pub type MembershipTree = MerkleTree<MerkleConfig>;
+
+pub fn new_membership_tree(
+    leaf_crh_params: &<LeafHash as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash as TwoToOneCRH>::Parameters,
+    leaves: &mut Vec<Poseidon381Field>,
+) -> MembershipTree {
+    clean_membership_list(leaves);
+    MembershipTree::new(leaf_crh_params, two_to_one_crh_params, leaves.as_ref()).unwrap()
+}
+
+/// Pads `leaves` to a power of two, and to at least two, with
+/// [`empty_leaf`](crate::commitments::empty_leaf).
+fn clean_membership_list(leaves: &mut Vec<Poseidon381Field>) {
+    let num_members = leaves.len();
+
+    let num_needed = if num_members == 1 {
+        1
+    } else {
+        num_members.next_power_of_two() - num_members
+    };
+
+    leaves.append(&mut vec![empty_leaf::<Poseidon381Field>(); num_needed]);
+}
+
+#[cfg(test)]
+mod membership_tree_tests {
+    use crate::{
+        commitments::poseidon381::common::{
+            clean_membership_list, new_membership_tree, LeafHash, MerklePath, TwoToOneHash,
+        },
+        member::Member,
+    };
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+
+    #[test]
+    fn three_leaves() {
+        let mut rng = ark_std::test_rng();
+        let params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+
+        let members = [Member::default(), Member::default(), Member::default()];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&params).unwrap())
+            .collect::<Vec<_>>();
+
+        clean_membership_list(&mut leaves);
+        assert_eq!(leaves.len(), 4); // Should add 1 more element to make it a power of two
+    }
+
+    #[test]
+    fn merkle_tree() {
+        let mut rng = ark_std::test_rng();
+        let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("2".into(), "2@usc.edu".into(), None),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+
+        let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let path: MerklePath = tree.generate_proof(2).unwrap();
+
+        assert!(path
+            .verify(
+                &leaf_crh_params,
+                &two_to_one_crh_params,
+                &tree.root(),
+                &members[2].hash::<LeafHash>(&leaf_crh_params).unwrap(),
+            )
+            .unwrap());
+    }
+}
@@ -0,0 +1,69 @@
+use super::common::{
+    LeafHash, LeafHashGadget, MerkleConfig, Poseidon381Field, TwoToOneHash, TwoToOneHashGadget,
+};
+use super::Poseidon381;
+use crate::commitments::GenericMerkleTreeCircuit;
+use ark_crypto_primitives::{
+    crh::{CRHGadget, TwoToOneCRHGadget},
+    PathVar,
+};
+
+/// R1CS representation of the Merkle tree root.
+pub type PoseidonRootVar =
+    <TwoToOneHashGadget as TwoToOneCRHGadget<TwoToOneHash, Poseidon381Field>>::OutputVar;
+
+pub type PoseidonLeafVar = <LeafHashGadget as CRHGadget<LeafHash, Poseidon381Field>>::OutputVar;
+
+/// R1CS representation of the Merkle tree path.
+pub type PoseidonPathVar =
+    PathVar<MerkleConfig, LeafHashGadget, TwoToOneHashGadget, Poseidon381Field>;
+
+pub type MerkleTreeCircuit = GenericMerkleTreeCircuit<Poseidon381>;
+
+#[cfg(test)]
+mod tests {
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+
+    use crate::{
+        commitments::poseidon381::{
+            common::{new_membership_tree, LeafHash, TwoToOneHash},
+            constraint::MerkleTreeCircuit,
+        },
+        member::Member,
+    };
+
+    #[test]
+    fn merkle_tree_constraints() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("1".into(), "1@usc.edu".into(), None),
+            Member::new("2".into(), "2@usc.edu".into(), None),
+            Member::new("3".into(), "3@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        // The path of leaf 1 only opens leaf 1
+        for (leaf, expected) in [(leaves[1], true), (leaves[2], false)] {
+            let circuit = MerkleTreeCircuit {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                leaf_hash: leaf,
+                authentication_path: Some(tree.generate_proof(1).unwrap()),
+            };
+
+            let cs = ConstraintSystem::new_ref();
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+}
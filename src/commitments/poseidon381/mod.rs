@@ -0,0 +1,69 @@
+pub mod common;
+pub mod constraint;
+
+pub use common::*;
+pub use constraint::*;
+
+use super::MerkleBackend;
+
+/// Marker for Poseidon over the BLS12-381 scalar field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Poseidon381;
+
+impl MerkleBackend for Poseidon381 {
+    type Field = Poseidon381Field;
+    type LeafHash = LeafHash;
+    type TwoToOneHash = TwoToOneHash;
+    type MerkleConfig = MerkleConfig;
+    type LeafHashGadget = LeafHashGadget;
+    type TwoToOneHashGadget = TwoToOneHashGadget;
+}
+
+#[cfg(test)]
+mod groth16_tests {
+    use super::constraint::*;
+    use crate::{
+        commitments::poseidon381::common::{new_membership_tree, LeafHash, TwoToOneHash},
+        member::Member,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH, SNARK};
+    use ark_groth16::Groth16;
+
+    #[test]
+    fn test_groth16_snark() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+            Member::new("carol".into(), "carol@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+
+        let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let circuit = MerkleTreeCircuit {
+            leaf_crh_params: leaf_crh_params.clone(),
+            two_to_one_crh_params,
+            root,
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(circuit.clone(), &mut rng)
+            .expect("setup failed");
+        let proof = Groth16::<Bls12_381>::prove(&pk, circuit, &mut rng).unwrap();
+
+        let verified = Groth16::<Bls12_381>::verify(&vk, &[root, leaves[1]], &proof)
+            .expect("verification failed");
+        assert!(verified, "SNARK proof verification failed");
+    }
+}
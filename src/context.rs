@@ -0,0 +1,22 @@
+use ark_ff::PrimeField;
+use blake2::{Blake2s, Digest};
+
+/// Maps an arbitrary message (a verifier nonce, a vote, a document to sign, ...)
+/// to the field element a proof is bound to.
+pub fn message_context<F: PrimeField>(message: &[u8]) -> F {
+    F::from_le_bytes_mod_order(&Blake2s::digest(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::message_context;
+    use ark_bls12_381::Fr;
+
+    #[test]
+    fn distinct_messages_give_distinct_contexts() {
+        let login = message_context::<Fr>(b"login nonce 1");
+
+        assert_eq!(login, message_context::<Fr>(b"login nonce 1"));
+        assert_ne!(login, message_context::<Fr>(b"login nonce 2"));
+    }
+}
@@ -0,0 +1,99 @@
+use super::BoweHopwoodCRHCompressor;
+use ark_crypto_primitives::crh::{
+    bowe_hopwood::constraints::{CRHGadget as BoweHopwoodCRHGadget, ParametersVar},
+    pedersen, CRHGadget, TwoToOneCRHGadget,
+};
+use ark_ec::TEModelParameters;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, uint8::UInt8};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::marker::PhantomData;
+
+pub struct BoweHopwoodCRHCompressorGadget<F: PrimeField> {
+    field: PhantomData<F>,
+}
+
+impl<F, P, W> CRHGadget<BoweHopwoodCRHCompressor<P, W>, F> for BoweHopwoodCRHCompressorGadget<F>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    W: pedersen::Window,
+{
+    type OutputVar = FpVar<F>;
+    type ParametersVar = ParametersVar<P, W>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        input: &[UInt8<F>],
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let point = <BoweHopwoodCRHGadget<P, FpVar<F>> as CRHGadget<
+            ark_crypto_primitives::crh::bowe_hopwood::CRH<P, W>,
+            F,
+        >>::evaluate(parameters, input)?;
+        Ok(point.x)
+    }
+}
+
+impl<F, P, W> TwoToOneCRHGadget<BoweHopwoodCRHCompressor<P, W>, F>
+    for BoweHopwoodCRHCompressorGadget<F>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    W: pedersen::Window,
+{
+    type OutputVar = FpVar<F>;
+    type ParametersVar = ParametersVar<P, W>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        left_input: &[UInt8<F>],
+        right_input: &[UInt8<F>],
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        assert_eq!(left_input.len(), right_input.len());
+        <Self as CRHGadget<BoweHopwoodCRHCompressor<P, W>, F>>::evaluate(
+            parameters,
+            &[left_input, right_input].concat(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_crypto_primitives::crh::{TwoToOneCRH, CRH};
+    use ark_ed_on_bls12_381::{EdwardsParameters, Fq};
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[derive(Clone)]
+    struct Window;
+    impl pedersen::Window for Window {
+        const WINDOW_SIZE: usize = 63;
+        const NUM_WINDOWS: usize = 4;
+    }
+
+    type Hash = BoweHopwoodCRHCompressor<EdwardsParameters, Window>;
+    type HashGadget = BoweHopwoodCRHCompressorGadget<Fq>;
+
+    #[test]
+    fn gadget_matches_native() {
+        let mut rng = ark_std::test_rng();
+        let parameters = <Hash as CRH>::setup(&mut rng).unwrap();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var =
+            ParametersVar::<EdwardsParameters, Window>::new_constant(cs.clone(), &parameters)
+                .unwrap();
+        let left = UInt8::new_witness_vec(cs.clone(), &[1; 32]).unwrap();
+        let right = UInt8::new_witness_vec(cs.clone(), &[2; 32]).unwrap();
+
+        let hash =
+            <HashGadget as TwoToOneCRHGadget<Hash, Fq>>::evaluate(&parameters_var, &left, &right)
+                .unwrap();
+        assert_eq!(
+            hash.value().unwrap(),
+            <Hash as TwoToOneCRH>::evaluate(&parameters, &[1; 32], &[2; 32]).unwrap()
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
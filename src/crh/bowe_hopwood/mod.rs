@@ -0,0 +1,59 @@
+//! Bowe–Hopwood Pedersen hash compressed to the x-coordinate of its output,
+//! usable as both the leaf and the two-to-one hash of a Merkle tree, like
+//! `PedersenCRHCompressor` with `TECompressor` for the plain windowed Pedersen.
+
+use ark_crypto_primitives::{
+    crh::{bowe_hopwood, pedersen, TwoToOneCRH, CRH},
+    Error,
+};
+use ark_ec::{ProjectiveCurve, TEModelParameters};
+use ark_std::{marker::PhantomData, rand::Rng};
+
+pub mod constraints;
+
+/// Number of input bits hashed by one generator.
+pub use bowe_hopwood::CHUNK_SIZE;
+
+pub struct BoweHopwoodCRHCompressor<P: TEModelParameters, W: pedersen::Window> {
+    group: PhantomData<P>,
+    window: PhantomData<W>,
+}
+
+impl<P: TEModelParameters, W: pedersen::Window> CRH for BoweHopwoodCRHCompressor<P, W> {
+    const INPUT_SIZE_BITS: usize = W::WINDOW_SIZE * W::NUM_WINDOWS * CHUNK_SIZE;
+    type Output = P::BaseField;
+    type Parameters = bowe_hopwood::Parameters<P>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <bowe_hopwood::CRH<P, W> as CRH>::setup(rng)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let point = <bowe_hopwood::CRH<P, W> as CRH>::evaluate(parameters, input)?;
+        Ok(point.into_affine().x)
+    }
+}
+
+impl<P: TEModelParameters, W: pedersen::Window> TwoToOneCRH for BoweHopwoodCRHCompressor<P, W> {
+    const LEFT_INPUT_SIZE_BITS: usize = <Self as CRH>::INPUT_SIZE_BITS / 2;
+    const RIGHT_INPUT_SIZE_BITS: usize = Self::LEFT_INPUT_SIZE_BITS;
+    type Output = P::BaseField;
+    type Parameters = bowe_hopwood::Parameters<P>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <Self as CRH>::setup(rng)
+    }
+
+    /// Hashes `left_input || right_input`; both halves must have the same length
+    /// so the concatenation is unambiguous.
+    fn evaluate(
+        parameters: &Self::Parameters,
+        left_input: &[u8],
+        right_input: &[u8],
+    ) -> Result<Self::Output, Error> {
+        if left_input.len() != right_input.len() {
+            return Err("left and right inputs must have the same length".into());
+        }
+        <Self as CRH>::evaluate(parameters, &[left_input, right_input].concat())
+    }
+}
@@ -0,0 +1,33 @@
+use ark_crypto_primitives::crh::{
+    injective_map::{InjectiveMap, PedersenCRHCompressor},
+    pedersen, CRH,
+};
+use ark_ec::{ProjectiveCurve, TEModelParameters};
+use ark_ff::PrimeField;
+use ark_sponge::Absorb;
+
+pub mod bowe_hopwood;
+pub mod poseidon;
+
+/// Longest input, in bytes, a hash accepts. `CRH::INPUT_SIZE_BITS` is the size
+/// of a blank leaf, which bounds the input of the windowed hashes but not of
+/// [`poseidon::PoseidonCRH`].
+pub trait MaxInput: CRH {
+    const MAX_INPUT_BYTES: usize;
+}
+
+impl<C: ProjectiveCurve, I: InjectiveMap<C>, W: pedersen::Window> MaxInput
+    for PedersenCRHCompressor<C, I, W>
+{
+    const MAX_INPUT_BYTES: usize = <Self as CRH>::INPUT_SIZE_BITS / 8;
+}
+
+impl<P: TEModelParameters, W: pedersen::Window> MaxInput
+    for bowe_hopwood::BoweHopwoodCRHCompressor<P, W>
+{
+    const MAX_INPUT_BYTES: usize = <Self as CRH>::INPUT_SIZE_BITS / 8;
+}
+
+impl<F: PrimeField + Absorb> MaxInput for poseidon::PoseidonCRH<F> {
+    const MAX_INPUT_BYTES: usize = usize::MAX;
+}
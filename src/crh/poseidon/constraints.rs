@@ -0,0 +1,156 @@
+use super::{chunk_bytes, element_bytes, PoseidonCRH, PoseidonParameters};
+use ark_crypto_primitives::crh::{CRHGadget, TwoToOneCRHGadget};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+use ark_sponge::constraints::CryptographicSpongeVar;
+use ark_sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_sponge::Absorb;
+use ark_std::{borrow::Borrow, marker::PhantomData};
+
+/// The parameters are embedded in the circuit as constants.
+#[derive(Clone)]
+pub struct PoseidonParametersVar<F: PrimeField> {
+    pub parameters: PoseidonParameters<F>,
+}
+
+impl<F: PrimeField> AllocVar<PoseidonParameters<F>, F> for PoseidonParametersVar<F> {
+    fn new_variable<T: Borrow<PoseidonParameters<F>>>(
+        _cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        _mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let parameters = f()?.borrow().clone();
+        Ok(Self { parameters })
+    }
+}
+
+pub struct PoseidonCRHGadget<F: PrimeField> {
+    field: PhantomData<F>,
+}
+
+impl<F: PrimeField> PoseidonCRHGadget<F> {
+    /// Same sponge as `PoseidonCRH::hash`.
+    fn hash(
+        parameters: &PoseidonParameters<F>,
+        elements: &[FpVar<F>],
+    ) -> Result<FpVar<F>, SynthesisError> {
+        let mut sponge = PoseidonSpongeVar::new(elements.cs(), &parameters.0);
+        sponge.absorb(&elements)?;
+        let [output]: [FpVar<F>; 1] = sponge
+            .squeeze_field_elements(1)?
+            .try_into()
+            .expect("one element was squeezed");
+        Ok(output)
+    }
+
+    /// Field element whose little-endian serialization is `bytes`, as a linear
+    /// combination of their bits. `bytes` must come from `ToBytesGadget`, which
+    /// already enforces that they encode a canonical field element.
+    fn element_from_bytes(bytes: &[UInt8<F>]) -> Result<FpVar<F>, SynthesisError> {
+        let mut element = FpVar::zero();
+        let mut power = F::one();
+        for byte in bytes {
+            for bit in byte.to_bits_le()? {
+                element += FpVar::from(bit) * power;
+                power.double_in_place();
+            }
+        }
+        Ok(element)
+    }
+}
+
+impl<F: PrimeField + Absorb> CRHGadget<PoseidonCRH<F>, F> for PoseidonCRHGadget<F> {
+    type OutputVar = FpVar<F>;
+    type ParametersVar = PoseidonParametersVar<F>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        input: &[UInt8<F>],
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let mut elements = vec![FpVar::constant(F::from(input.len() as u64))];
+        for chunk in input.chunks(chunk_bytes::<F>()) {
+            let bits = chunk
+                .iter()
+                .map(|byte| byte.to_bits_le())
+                .collect::<Result<Vec<_>, _>>()?
+                .concat();
+            elements.push(Boolean::le_bits_to_fp_var(&bits)?);
+        }
+        Self::hash(&parameters.parameters, &elements)
+    }
+}
+
+impl<F: PrimeField + Absorb> TwoToOneCRHGadget<PoseidonCRH<F>, F> for PoseidonCRHGadget<F> {
+    type OutputVar = FpVar<F>;
+    type ParametersVar = PoseidonParametersVar<F>;
+
+    fn evaluate(
+        parameters: &Self::ParametersVar,
+        left_input: &[UInt8<F>],
+        right_input: &[UInt8<F>],
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        if left_input.len() > element_bytes::<F>() || right_input.len() > element_bytes::<F>() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let elements = [
+            Self::element_from_bytes(left_input)?,
+            Self::element_from_bytes(right_input)?,
+        ];
+        Self::hash(&parameters.parameters, &elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_crypto_primitives::crh::{TwoToOneCRH, CRH};
+    use ark_ff::{to_bytes, UniformRand};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn gadget_matches_native() {
+        let mut rng = ark_std::test_rng();
+        let parameters = <PoseidonCRH<Fr> as CRH>::setup(&mut rng).unwrap();
+
+        for input in [&b""[..], b"alice", &[7; 70]] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let parameters_var =
+                PoseidonParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+            let input_var = UInt8::new_witness_vec(cs.clone(), input).unwrap();
+
+            let hash =
+                <PoseidonCRHGadget<Fr> as CRHGadget<_, Fr>>::evaluate(&parameters_var, &input_var)
+                    .unwrap();
+            assert_eq!(
+                hash.value().unwrap(),
+                <PoseidonCRH<Fr> as CRH>::evaluate(&parameters, input).unwrap()
+            );
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        let (left, right) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let parameters_var = PoseidonParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let left_var = FpVar::new_witness(cs.clone(), || Ok(left)).unwrap();
+        let right_var = FpVar::new_witness(cs.clone(), || Ok(right)).unwrap();
+        let hash = <PoseidonCRHGadget<Fr> as TwoToOneCRHGadget<_, Fr>>::evaluate(
+            &parameters_var,
+            &left_var.to_bytes().unwrap(),
+            &right_var.to_bytes().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            hash.value().unwrap(),
+            <PoseidonCRH<Fr> as TwoToOneCRH>::evaluate(
+                &parameters,
+                &to_bytes![left].unwrap(),
+                &to_bytes![right].unwrap()
+            )
+            .unwrap()
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,145 @@
+//! Round constants and MDS matrix of Poseidon, derived with the Grain LFSR of
+//! the Poseidon paper's reference implementation
+//! (`generate_parameters_grain.sage`, <https://eprint.iacr.org/2019/458>), so
+//! they match the published parameters of other implementations such as
+//! circomlib's.
+
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+
+/// Grain LFSR seeded with the shape of a Poseidon instance over a prime field
+/// with the `x^alpha` S-box.
+struct GrainLfsr {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl GrainLfsr {
+    fn new(field_bits: u64, width: u64, full_rounds: u64, partial_rounds: u64) -> Self {
+        let mut state = [false; 80];
+        // b0, b1: prime field; b2..b5: x^alpha S-box
+        state[1] = true;
+        // b6..b17: field size, b18..b29: width, b30..b39: full rounds,
+        // b40..b49: partial rounds, each big-endian
+        for (range, value) in [
+            (6..18, field_bits),
+            (18..30, width),
+            (30..40, full_rounds),
+            (40..50, partial_rounds),
+        ] {
+            for (i, bit) in range.rev().enumerate() {
+                state[bit] = (value >> i) & 1 == 1;
+            }
+        }
+        // b50..b79
+        state[50..].fill(true);
+
+        let mut lfsr = Self { state, head: 0 };
+        for _ in 0..160 {
+            lfsr.update();
+        }
+        lfsr
+    }
+
+    fn update(&mut self) -> bool {
+        let bit = [62, 51, 38, 23, 13, 0]
+            .iter()
+            .fold(false, |bit, tap| bit ^ self.state[(self.head + tap) % 80]);
+        self.state[self.head] = bit;
+        self.head = (self.head + 1) % 80;
+        bit
+    }
+
+    /// Next `num_bits` bits of the self-shrinking generator, most significant
+    /// first: of each pair of LFSR bits, the second is kept if the first is set.
+    fn bits(&mut self, num_bits: usize) -> Vec<bool> {
+        (0..num_bits)
+            .map(|_| {
+                while !self.update() {
+                    self.update();
+                }
+                self.update()
+            })
+            .collect()
+    }
+
+    /// Next field element, sampling again while the bits exceed the modulus.
+    fn field_element_rejection_sampling<F: PrimeField>(&mut self) -> F {
+        loop {
+            let bits = self.bits(F::Params::MODULUS_BITS as usize);
+            if let Some(element) = F::from_repr(F::BigInt::from_bits_be(&bits)) {
+                return element;
+            }
+        }
+    }
+
+    /// Next field element, reducing the bits modulo the field's prime.
+    fn field_element_mod_p<F: PrimeField>(&mut self) -> F {
+        let bits = self.bits(F::Params::MODULUS_BITS as usize);
+        F::from_le_bytes_mod_order(&F::BigInt::from_bits_be(&bits).to_bytes_le())
+    }
+}
+
+/// Round constants, one row per round, and Cauchy MDS matrix `1 / (x_i + y_j)`
+/// of a Poseidon instance of `width` elements.
+pub(super) fn ark_and_mds<F: PrimeField>(
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+) -> (Vec<Vec<F>>, Vec<Vec<F>>) {
+    let mut lfsr = GrainLfsr::new(
+        F::Params::MODULUS_BITS as u64,
+        width as u64,
+        full_rounds as u64,
+        partial_rounds as u64,
+    );
+
+    let ark = (0..full_rounds + partial_rounds)
+        .map(|_| {
+            (0..width)
+                .map(|_| lfsr.field_element_rejection_sampling())
+                .collect()
+        })
+        .collect();
+
+    let xs = (0..width)
+        .map(|_| lfsr.field_element_mod_p())
+        .collect::<Vec<F>>();
+    let ys = (0..width)
+        .map(|_| lfsr.field_element_mod_p())
+        .collect::<Vec<F>>();
+    let mds = xs
+        .iter()
+        .map(|x| ys.iter().map(|y| (*x + y).inverse().unwrap()).collect())
+        .collect();
+
+    (ark, mds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use core::str::FromStr;
+
+    #[test]
+    fn matches_circomlib_parameters() {
+        // circomlib's Poseidon over BN254 with a width-3 state
+        let (ark, mds) = ark_and_mds::<Fr>(3, 8, 57);
+
+        assert_eq!(ark.len(), 65);
+        assert_eq!(
+            ark[0][0],
+            Fr::from_str(
+                "6745197990210204598374042828761989596302876299545964402857411729872131034734"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            mds[0][0],
+            Fr::from_str(
+                "7511745149465107256748700652201246547602992235352608707588321460060273774987"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Poseidon hash over a prime field, usable as both the leaf and the two-to-one
+//! hash of a Merkle tree: ark-sponge's Poseidon sponge of width 3 (rate 2,
+//! capacity 1).
+//!
+//! The parameters are the standard ones of the Poseidon paper for a width-3
+//! state over a ~255-bit field at the 128-bit security level: the `x^5` S-box,
+//! 8 full and 57 partial rounds, and round constants and MDS matrix derived
+//! with the paper's Grain LFSR, as in circomlib. They are fixed, so `setup`
+//! ignores its randomness.
+
+use ark_crypto_primitives::{
+    crh::{TwoToOneCRH, CRH},
+    Error,
+};
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use ark_sponge::poseidon::{PoseidonParameters as SpongeParameters, PoseidonSponge};
+use ark_sponge::{Absorb, CryptographicSponge, FieldBasedCryptographicSponge};
+use ark_std::{marker::PhantomData, rand::Rng};
+
+pub mod constraints;
+mod grain;
+
+/// Width of the Poseidon state, the only one ark-sponge supports.
+pub const WIDTH: usize = 3;
+
+/// Exponent of the S-box. It must be coprime with `p - 1`, which holds for the
+/// scalar fields of BN254 and BLS12-381.
+pub const ALPHA: u64 = 5;
+
+/// Number of full rounds, half of them before and half after the partial ones.
+pub const FULL_ROUNDS: usize = 8;
+
+/// Number of partial rounds.
+pub const PARTIAL_ROUNDS: usize = 57;
+
+/// Parameters of the ark-sponge Poseidon sponge. The default ones are the
+/// standard parameters described in the module documentation.
+#[derive(Clone, Debug)]
+pub struct PoseidonParameters<F: PrimeField>(pub SpongeParameters<F>);
+
+impl<F: PrimeField> Default for PoseidonParameters<F> {
+    fn default() -> Self {
+        let (ark, mds) = grain::ark_and_mds::<F>(WIDTH, FULL_ROUNDS, PARTIAL_ROUNDS);
+        Self(SpongeParameters::new(
+            FULL_ROUNDS as u32,
+            PARTIAL_ROUNDS as u32,
+            ALPHA,
+            mds,
+            ark,
+        ))
+    }
+}
+
+/// Poseidon hash of bytes and of pairs of field elements.
+///
+/// As a [`CRH`], the sponge absorbs the input length in bytes followed by the
+/// input packed into field elements of [`chunk_bytes`] bytes each, and inputs
+/// of any length are accepted; `INPUT_SIZE_BITS` is the size of one serialized
+/// field element, the leaf type of `MerkleTreeCircuit`.
+///
+/// As a [`TwoToOneCRH`], both inputs must be serialized field elements, which
+/// the sponge absorbs as they are.
+pub struct PoseidonCRH<F: PrimeField> {
+    field: PhantomData<F>,
+}
+
+/// Number of input bytes packed into one field element, so that packing is
+/// injective.
+pub fn chunk_bytes<F: PrimeField>() -> usize {
+    (F::Params::MODULUS_BITS as usize - 1) / 8
+}
+
+/// Serialized size of a field element, the size of each two-to-one input.
+fn element_bytes<F: PrimeField>() -> usize {
+    <F::BigInt as BigInteger>::NUM_LIMBS * 8
+}
+
+impl<F: PrimeField + Absorb> PoseidonCRH<F> {
+    fn hash(parameters: &PoseidonParameters<F>, elements: &[F]) -> F {
+        let mut sponge = PoseidonSponge::new(&parameters.0);
+        sponge.absorb(&elements);
+        sponge.squeeze_native_field_elements(1)[0]
+    }
+}
+
+impl<F: PrimeField + Absorb> CRH for PoseidonCRH<F> {
+    const INPUT_SIZE_BITS: usize = <F::BigInt as BigInteger>::NUM_LIMBS * 64;
+    type Output = F;
+    type Parameters = PoseidonParameters<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(PoseidonParameters::default())
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let elements = core::iter::once(F::from(input.len() as u64))
+            .chain(
+                input
+                    .chunks(chunk_bytes::<F>())
+                    .map(F::from_le_bytes_mod_order),
+            )
+            .collect::<Vec<_>>();
+        Ok(Self::hash(parameters, &elements))
+    }
+}
+
+impl<F: PrimeField + Absorb> TwoToOneCRH for PoseidonCRH<F> {
+    const LEFT_INPUT_SIZE_BITS: usize = <Self as CRH>::INPUT_SIZE_BITS;
+    const RIGHT_INPUT_SIZE_BITS: usize = <Self as CRH>::INPUT_SIZE_BITS;
+    type Output = F;
+    type Parameters = PoseidonParameters<F>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        <Self as CRH>::setup(rng)
+    }
+
+    /// Hashes the field elements serialized in `left_input` and `right_input`.
+    fn evaluate(
+        parameters: &Self::Parameters,
+        left_input: &[u8],
+        right_input: &[u8],
+    ) -> Result<Self::Output, Error> {
+        if left_input.len() > element_bytes::<F>() || right_input.len() > element_bytes::<F>() {
+            return Err("inputs must be serialized field elements".into());
+        }
+
+        let elements = [
+            F::from_le_bytes_mod_order(left_input),
+            F::from_le_bytes_mod_order(right_input),
+        ];
+        Ok(Self::hash(parameters, &elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_ff::{to_bytes, UniformRand};
+
+    #[test]
+    fn hashes_are_input_sensitive() {
+        let mut rng = ark_std::test_rng();
+        let parameters = <PoseidonCRH<Fr> as CRH>::setup(&mut rng).unwrap();
+        let hash = |input: &[u8]| <PoseidonCRH<Fr> as CRH>::evaluate(&parameters, input).unwrap();
+
+        assert_eq!(hash(b"alice"), hash(b"alice"));
+        assert_ne!(hash(b"alice"), hash(b"bob"));
+        // Trailing zeros pack to the same elements but change the length
+        assert_ne!(hash(&[0; 1]), hash(&[0; 2]));
+    }
+
+    #[test]
+    fn two_to_one_absorbs_field_elements() {
+        let mut rng = ark_std::test_rng();
+        let parameters = <PoseidonCRH<Fr> as TwoToOneCRH>::setup(&mut rng).unwrap();
+        let (left, right) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+        let hash = <PoseidonCRH<Fr> as TwoToOneCRH>::evaluate(
+            &parameters,
+            &to_bytes![left].unwrap(),
+            &to_bytes![right].unwrap(),
+        )
+        .unwrap();
+        assert_eq!(hash, PoseidonCRH::hash(&parameters, &[left, right]));
+
+        // Longer inputs would wrap around the modulus
+        assert!(
+            <PoseidonCRH<Fr> as TwoToOneCRH>::evaluate(&parameters, &[1; 33], &[2; 32]).is_err()
+        );
+    }
+}
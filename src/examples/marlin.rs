@@ -1,10 +1,5 @@
 use ark_bls12_381::{Bls12_381, Fr};
 use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
-use ark_marlin::Marlin;
-use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::marlin_pc::MarlinKZG10;
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
-use blake2::Blake2s;
 
 use crate::commitments::pedersen381::{
     common::{
@@ -13,9 +8,7 @@ use crate::commitments::pedersen381::{
     },
     MerkleTreeCircuit,
 };
-
-type PC = MarlinKZG10<Bls12_381, DensePolynomial<Fr>>;
-type MarlinM = Marlin<Fr, PC, Blake2s>;
+use crate::marlin::{prove, verify, KzgPC, MembershipSrs};
 
 #[test]
 fn create_test_circuit() {
@@ -32,7 +25,7 @@ fn create_test_circuit() {
     // Hash each member to get leaves
     let mut leaves: Vec<_> = members
         .iter()
-        .map(|m| m.hash::<LeafHash>(&leaf_crh_params))
+        .map(|m| m.hash::<LeafHash>(&leaf_crh_params).unwrap())
         .collect();
 
     // Create tree
@@ -43,7 +36,7 @@ fn create_test_circuit() {
     // Prove inclusion of member at index 2
     let index = rand::random_range(..4);
     let path: MerklePath = tree.generate_proof(index).unwrap();
-    let member_hash = members[index].hash::<LeafHash>(&leaf_crh_params);
+    let member_hash = members[index].hash::<LeafHash>(&leaf_crh_params).unwrap();
 
     // Build circuit
     let circuit = MerkleTreeCircuit {
@@ -54,34 +47,15 @@ fn create_test_circuit() {
         authentication_path: Some(path),
     };
 
-    // Universal setup for the circuit
-    let cs = ConstraintSystem::<Fr>::new_ref();
-    circuit.clone().generate_constraints(cs.clone()).unwrap();
-
-    // NOTE: Correctness requires every later circuit to satisfy:
-    //	- rows ≤ num_constraints
-    let n_constraints = cs.num_constraints();
-    //	- vars ≤ num_variables
-    let n_variables = cs.num_instance_variables() + cs.num_witness_variables();
-    //	- non-zeros ≤ num_non_zero
-    let n_non_zero = 5 * n_constraints;
-
-    let srs = MarlinM::universal_setup(
-        n_constraints.next_power_of_two(),
-        n_variables.next_power_of_two(),
-        n_non_zero.next_power_of_two(),
-        &mut rng,
-    )
-    .unwrap();
-
-    // One-time setup for the circuit
-    let (pk, vk) = MarlinM::index(&srs, circuit.clone()).unwrap();
+    // Universal setup sized for the circuit, then one-time setup for the circuit
+    let srs = MembershipSrs::<Fr, KzgPC<Bls12_381>>::for_circuit(circuit.clone(), &mut rng).unwrap();
+    let (pk, vk) = srs.index(circuit.clone()).unwrap();
 
-    let proof = MarlinM::prove(&pk, circuit.clone(), &mut rng).unwrap();
+    let proof = prove(&pk, circuit.clone(), &mut rng).unwrap();
 
     let inputs = public_inputs(&root, &member_hash);
 
-    assert!(MarlinM::verify(&vk, &inputs, &proof, &mut rng).unwrap());
+    assert!(verify(&vk, &inputs, &proof, &mut rng).unwrap());
 }
 
 fn public_inputs(root: &Root, leaf: &Pedersen381Field) -> Vec<Pedersen381Field> {
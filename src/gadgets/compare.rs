@@ -0,0 +1,49 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::SynthesisError;
+
+/// Whether `a < b`, comparing the canonical representations of two arbitrary
+/// field elements (unlike `FpVar::is_cmp`, which requires both to be at most
+/// `(p - 1) / 2`).
+pub fn is_less_than<F: PrimeField>(
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+) -> Result<Boolean<F>, SynthesisError> {
+    let a_bits = a.to_bits_le()?;
+    let b_bits = b.to_bits_le()?;
+
+    // Scan from the least significant bit: a higher differing bit overrides
+    // whatever was decided by the lower ones.
+    let mut less = Boolean::FALSE;
+    for (a_bit, b_bit) in a_bits.iter().zip(&b_bits) {
+        let differ = a_bit.xor(b_bit)?;
+        less = Boolean::conditionally_select(&differ, b_bit, &less)?;
+    }
+    Ok(less)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn compares_field_elements() {
+        let large = -Fr::from(1u64);
+        for (a, b, expected) in [
+            (Fr::from(1u64), Fr::from(2u64), true),
+            (Fr::from(2u64), Fr::from(2u64), false),
+            (Fr::from(3u64), Fr::from(2u64), false),
+            (Fr::from(3u64), large, true),
+            (large, Fr::from(3u64), false),
+        ] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let a = FpVar::new_witness(cs.clone(), || Ok(a)).unwrap();
+            let b = FpVar::new_witness(cs.clone(), || Ok(b)).unwrap();
+
+            assert_eq!(is_less_than(&a, &b).unwrap().value().unwrap(), expected);
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::gadgets::range::enforce_at_least;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::SynthesisError;
+
+/// Longest email domain that fits, together with its length, in one field
+/// element of every supported backend.
+pub const MAX_DOMAIN_BYTES: usize = 30;
+
+/// Packs `domain` as the public input of the email domain circuits: its length
+/// followed by its bytes, zero-padded to [`MAX_DOMAIN_BYTES`], little-endian.
+pub fn domain_to_field<F: PrimeField>(domain: &str) -> F {
+    assert!(
+        domain.len() <= MAX_DOMAIN_BYTES,
+        "email domain is longer than {} bytes",
+        MAX_DOMAIN_BYTES
+    );
+
+    let mut bytes = vec![domain.len() as u8];
+    bytes.extend_from_slice(domain.as_bytes());
+    F::from_le_bytes_mod_order(&bytes)
+}
+
+/// Enforces that the email stored at `bytes[email_start..email_start + email_len]`
+/// ends with `@` followed by the public `domain` (encoded by [`domain_to_field`]).
+///
+/// The email may start anywhere: the start of the suffix is selected with a
+/// one-hot vector, so the cost is linear in `bytes.len() * MAX_DOMAIN_BYTES`.
+pub fn enforce_email_domain<F: PrimeField>(
+    bytes: &[UInt8<F>],
+    email_start: &FpVar<F>,
+    email_len: &FpVar<F>,
+    domain: &FpVar<F>,
+) -> Result<(), SynthesisError> {
+    let cs = domain.cs();
+
+    // Unpack the public domain into its length and bytes
+    let encoded_value = domain
+        .value()
+        .ok()
+        .map(|domain| domain.into_repr().to_bytes_le());
+    let encoded = (0..=MAX_DOMAIN_BYTES)
+        .map(|i| {
+            UInt8::new_witness(ark_relations::ns!(cs, "domain_byte"), || {
+                encoded_value
+                    .as_ref()
+                    .map(|bytes| bytes[i])
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let encoded_bits = encoded
+        .iter()
+        .map(|byte| byte.to_bits_le())
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+    Boolean::le_bits_to_fp_var(&encoded_bits)?.enforce_equal(domain)?;
+
+    let domain_len = byte_to_fp_var(&encoded[0])?;
+    let domain_len_value = encoded_value.as_ref().map(|bytes| bytes[0] as usize);
+
+    // `active[j]` tells whether position `j` of `@<domain>` is compared
+    let active = (0..=MAX_DOMAIN_BYTES)
+        .map(|j| {
+            Boolean::new_witness(ark_relations::ns!(cs, "active"), || {
+                domain_len_value
+                    .map(|len| j <= len)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    active[0].enforce_equal(&Boolean::TRUE)?;
+    for j in 1..active.len() {
+        active[j]
+            .and(&active[j - 1].not())?
+            .enforce_equal(&Boolean::FALSE)?;
+    }
+    active
+        .iter()
+        .fold(FpVar::zero(), |sum, bit| sum + FpVar::from(bit.clone()))
+        .enforce_equal(&(&domain_len + FpVar::one()))?;
+
+    // The `@` must belong to the email
+    enforce_at_least(email_len, &(&domain_len + FpVar::one()), 8)?;
+    let start = email_start + email_len - &domain_len - FpVar::one();
+
+    // Select the position of the `@`
+    let start_value = start.value().ok();
+    let selector = (0..bytes.len())
+        .map(|k| {
+            Boolean::new_witness(ark_relations::ns!(cs, "selector"), || {
+                start_value
+                    .map(|start| F::from(k as u64) == start)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    selector
+        .iter()
+        .fold(FpVar::zero(), |sum, bit| sum + FpVar::from(bit.clone()))
+        .enforce_equal(&FpVar::one())?;
+    selector
+        .iter()
+        .enumerate()
+        .fold(FpVar::zero(), |sum, (k, bit)| {
+            sum + FpVar::from(bit.clone()) * F::from(k as u64)
+        })
+        .enforce_equal(&start)?;
+
+    let values = bytes
+        .iter()
+        .map(byte_to_fp_var)
+        .collect::<Result<Vec<_>, _>>()?;
+    for j in 0..=MAX_DOMAIN_BYTES {
+        let mut actual = FpVar::zero();
+        for (k, bit) in selector.iter().enumerate() {
+            if let Some(value) = values.get(k + j) {
+                actual += FpVar::from(bit.clone()) * value;
+            }
+        }
+
+        let expected = if j == 0 {
+            FpVar::constant(F::from(b'@'))
+        } else {
+            byte_to_fp_var(&encoded[j])?
+        };
+        actual.conditional_enforce_equal(&expected, &active[j])?;
+    }
+
+    Ok(())
+}
+
+/// The value of `byte` as a field element.
+pub fn byte_to_fp_var<F: PrimeField>(byte: &UInt8<F>) -> Result<FpVar<F>, SynthesisError> {
+    Boolean::le_bits_to_fp_var(&byte.to_bits_le()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn checks_email_domain() {
+        let record = b"\x05\x09id-42a@usc.edu\x00\x00";
+        for (domain, expected) in [
+            ("usc.edu", true),
+            ("edu", false),
+            ("sc.edu", false),
+            ("ucla.edu", false),
+            ("a@usc.edu", false),
+        ] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let bytes = UInt8::new_witness_vec(cs.clone(), record).unwrap();
+            let email_start = byte_to_fp_var(&bytes[0]).unwrap() + FpVar::constant(Fr::from(2u64));
+            let email_len = byte_to_fp_var(&bytes[1]).unwrap();
+            let domain_var =
+                FpVar::new_input(cs.clone(), || Ok(domain_to_field::<Fr>(domain))).unwrap();
+
+            enforce_email_domain(&bytes, &email_start, &email_len, &domain_var).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected, "{}", domain);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod compare;
+pub mod domain;
+pub mod range;
+pub mod select;
+pub mod timestamp;
@@ -0,0 +1,76 @@
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::SynthesisError;
+
+/// Enforces `0 <= value < 2^num_bits` by decomposing `value` into `num_bits`
+/// witness bits. Differences that went "negative" wrap around the field and
+/// fail this check, which is what the comparisons below rely on.
+pub fn enforce_in_range<F: PrimeField>(
+    value: &FpVar<F>,
+    num_bits: usize,
+) -> Result<(), SynthesisError> {
+    assert!(num_bits < F::Params::CAPACITY as usize);
+
+    let cs = value.cs();
+    let value_bits = value
+        .value()
+        .ok()
+        .map(|value| value.into_repr().to_bits_le());
+
+    let bits = (0..num_bits)
+        .map(|i| {
+            Boolean::new_witness(ark_relations::ns!(cs, "bit"), || {
+                value_bits
+                    .as_ref()
+                    .map(|bits| bits[i])
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(value)
+}
+
+/// Enforces `lower <= value`, where both are known to be smaller than
+/// `2^num_bits`.
+pub fn enforce_at_least<F: PrimeField>(
+    value: &FpVar<F>,
+    lower: &FpVar<F>,
+    num_bits: usize,
+) -> Result<(), SynthesisError> {
+    enforce_in_range(&(value - lower), num_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn range_checks() {
+        for (value, num_bits, expected) in [(0, 8, true), (255, 8, true), (256, 8, false)] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let value = FpVar::new_witness(cs.clone(), || Ok(Fr::from(value as u64))).unwrap();
+            enforce_in_range(&value, num_bits).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+
+        // -1 wraps around to a huge field element
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let value = FpVar::new_witness(cs.clone(), || Ok(-Fr::from(1u64))).unwrap();
+        enforce_in_range(&value, 64).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn lower_bounds() {
+        for (value, lower, expected) in [(10u64, 9u64, true), (10, 10, true), (10, 11, false)] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let value = FpVar::new_witness(cs.clone(), || Ok(Fr::from(value))).unwrap();
+            let lower = FpVar::new_input(cs.clone(), || Ok(Fr::from(lower))).unwrap();
+            enforce_at_least(&value, &lower, 64).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+/// Returns `values[index]` for a private `index`, without revealing it: the
+/// index is witnessed as a one-hot vector of bits summing to one.
+pub fn select_private<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    values: &[FpVar<F>],
+    index: Option<usize>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let selector = (0..values.len())
+        .map(|i| {
+            Boolean::new_witness(ark_relations::ns!(cs, "selector"), || {
+                index
+                    .map(|index| index == i)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    selector
+        .iter()
+        .fold(FpVar::zero(), |sum, bit| sum + FpVar::from(bit.clone()))
+        .enforce_equal(&FpVar::one())?;
+
+    let mut selected = FpVar::zero();
+    for (bit, value) in selector.iter().zip(values) {
+        selected += FpVar::from(bit.clone()) * value;
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn selects_private_index() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let values =
+            [3u64, 5, 7].map(|value| FpVar::new_input(cs.clone(), || Ok(Fr::from(value))).unwrap());
+
+        let selected = select_private(cs.clone(), &values, Some(1)).unwrap();
+        assert_eq!(selected.value().unwrap(), Fr::from(5u64));
+        assert!(cs.is_satisfied().unwrap());
+
+        // Out of range indices select nothing and are rejected
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let values = [FpVar::new_input(cs.clone(), || Ok(Fr::from(3u64))).unwrap()];
+        let _ = select_private(cs.clone(), &values, Some(1)).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::gadgets::range::{enforce_at_least, enforce_in_range};
+use crate::member::TIMESTAMP_BYTES;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::SynthesisError;
+use chrono::{DateTime, Duration, Utc};
+
+/// Timestamps are compared as unsigned 64-bit integers, so dates before the
+/// UNIX epoch are not supported inside circuits.
+pub const TIMESTAMP_BITS: usize = 8 * TIMESTAMP_BYTES;
+
+/// Encodes `date` the way circuits compare timestamps.
+pub fn timestamp_to_field<F: PrimeField>(date: &DateTime<Utc>) -> F {
+    F::from(date.timestamp() as u64)
+}
+
+/// Reads a big-endian timestamp, as written by `Member::to_bytes`.
+pub fn timestamp_from_be_bytes<F: PrimeField>(
+    bytes: &[UInt8<F>],
+) -> Result<FpVar<F>, SynthesisError> {
+    assert_eq!(bytes.len(), TIMESTAMP_BYTES);
+
+    let mut bits = Vec::with_capacity(TIMESTAMP_BITS);
+    for byte in bytes.iter().rev() {
+        bits.extend(byte.to_bits_le()?);
+    }
+    Boolean::le_bits_to_fp_var(&bits)
+}
+
+/// Enforces `earlier <= later`.
+pub fn enforce_not_after<F: PrimeField>(
+    earlier: &FpVar<F>,
+    later: &FpVar<F>,
+) -> Result<(), SynthesisError> {
+    enforce_at_least(later, earlier, TIMESTAMP_BITS)
+}
+
+/// Encodes `duration` in seconds, the unit circuits compare timestamps in.
+/// Negative durations are not supported.
+pub fn duration_to_field<F: PrimeField>(duration: &Duration) -> F {
+    F::from(duration.num_seconds() as u64)
+}
+
+/// Enforces `later - earlier >= duration`.
+pub fn enforce_elapsed_at_least<F: PrimeField>(
+    earlier: &FpVar<F>,
+    later: &FpVar<F>,
+    duration: &FpVar<F>,
+) -> Result<(), SynthesisError> {
+    enforce_at_least(&(later - earlier), duration, TIMESTAMP_BITS)
+}
+
+/// Enforces `earlier < later` when `condition` is set.
+pub fn conditional_enforce_before<F: PrimeField>(
+    earlier: &FpVar<F>,
+    later: &FpVar<F>,
+    condition: &Boolean<F>,
+) -> Result<(), SynthesisError> {
+    let gap = (later - earlier - FpVar::one()) * FpVar::from(condition.clone());
+    enforce_in_range(&gap, TIMESTAMP_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    fn witness(cs: ark_relations::r1cs::ConstraintSystemRef<Fr>, value: u64) -> FpVar<Fr> {
+        FpVar::new_witness(cs, || Ok(Fr::from(value))).unwrap()
+    }
+
+    #[test]
+    fn decodes_big_endian_timestamps() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let timestamp = 1_700_000_000i64;
+
+        let bytes = UInt8::new_witness_vec(cs.clone(), &timestamp.to_be_bytes()).unwrap();
+        let decoded = timestamp_from_be_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.value().unwrap(), Fr::from(timestamp as u64));
+    }
+
+    #[test]
+    fn orders_timestamps() {
+        for (earlier, later, expected) in [(1, 2, true), (2, 2, true), (3, 2, false)] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let (earlier, later) = (witness(cs.clone(), earlier), witness(cs.clone(), later));
+            enforce_not_after(&earlier, &later).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+
+        for (earlier, later, condition, expected) in [
+            (1, 2, true, true),
+            (2, 2, true, false),
+            (2, 2, false, true),
+            (3, 2, false, true),
+        ] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let (earlier, later) = (witness(cs.clone(), earlier), witness(cs.clone(), later));
+            let condition = Boolean::new_witness(cs.clone(), || Ok(condition)).unwrap();
+            conditional_enforce_before(&earlier, &later, &condition).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn measures_elapsed_time() {
+        for (earlier, later, duration, expected) in [
+            (1, 11, 10, true),
+            (1, 11, 9, true),
+            (1, 11, 11, false),
+            (11, 1, 0, false),
+        ] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let (earlier, later) = (witness(cs.clone(), earlier), witness(cs.clone(), later));
+            let duration = witness(cs.clone(), duration);
+            enforce_elapsed_at_least(&earlier, &later, &duration).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+}
@@ -1,2 +1,10 @@
+pub mod ceremony;
 pub mod commitments;
+pub mod context;
+pub mod crh;
+pub mod gadgets;
+pub mod marlin;
 pub mod member;
+pub mod nullifier;
+pub mod powers_of_tau;
+pub mod proof_system;
@@ -1,7 +1,48 @@
 #[cfg(feature = "cli")]
 compile_error!("CLI feature not stable");
 
+// The backend features pick which backend the CLI runs, so only one may be set
+#[cfg(any(
+    all(feature = "pedersen254", feature = "pedersen377"),
+    all(feature = "pedersen254", feature = "pedersen381"),
+    all(feature = "pedersen254", feature = "pedersen761"),
+    all(feature = "pedersen254", feature = "poseidon381"),
+    all(feature = "pedersen377", feature = "pedersen381"),
+    all(feature = "pedersen377", feature = "pedersen761"),
+    all(feature = "pedersen377", feature = "poseidon381"),
+    all(feature = "pedersen381", feature = "pedersen761"),
+    all(feature = "pedersen381", feature = "poseidon381"),
+    all(feature = "pedersen761", feature = "poseidon381"),
+))]
+compile_error!(
+    "Only one of the pedersen254, pedersen377, pedersen381, pedersen761 and poseidon381 features can be enabled."
+);
+
 // Conditional imports for pedersen modules
+#[cfg(feature = "pedersen254")]
+mod pedersen254 {
+    pub use ark_bn254::Bn254;
+    pub use zkmember::commitments::pedersen254::{
+        new_membership_tree, LeafHash, MerkleTreeCircuit, Pedersen254Field as PedersenField, Root,
+        TwoToOneHash,
+    };
+    pub type Curve = Bn254;
+}
+#[cfg(feature = "pedersen254")]
+use pedersen254::*;
+
+#[cfg(feature = "pedersen377")]
+mod pedersen377 {
+    pub use ark_bls12_377::Bls12_377;
+    pub use zkmember::commitments::pedersen377::{
+        new_membership_tree, LeafHash, MerkleTreeCircuit, Pedersen377Field as PedersenField, Root,
+        TwoToOneHash,
+    };
+    pub type Curve = Bls12_377;
+}
+#[cfg(feature = "pedersen377")]
+use pedersen377::*;
+
 #[cfg(feature = "pedersen381")]
 mod pedersen381 {
     pub use ark_bls12_381::Bls12_381;
@@ -27,7 +68,25 @@ mod pedersen761 {
 #[cfg(feature = "pedersen761")]
 use pedersen761::*;
 
-#[cfg(any(feature = "pedersen381", feature = "pedersen761"))]
+#[cfg(feature = "poseidon381")]
+mod poseidon381 {
+    pub use ark_bls12_381::Bls12_381;
+    pub use zkmember::commitments::poseidon381::{
+        new_membership_tree, LeafHash, MerkleTreeCircuit, Poseidon381Field as PedersenField, Root,
+        TwoToOneHash,
+    };
+    pub type Curve = Bls12_381;
+}
+#[cfg(feature = "poseidon381")]
+use poseidon381::*;
+
+#[cfg(any(
+    feature = "pedersen254",
+    feature = "pedersen377",
+    feature = "pedersen381",
+    feature = "pedersen761",
+    feature = "poseidon381"
+))]
 fn exec_marlin(num_members: usize) {
     use ark_crypto_primitives::crh::{TwoToOneCRH, CRH};
     use ark_groth16::{Groth16, Proof, VerifyingKey};
@@ -43,11 +102,26 @@ pub fn main() {
     #[cfg(feature = "cli")]
     exec_cli();
 
-    #[cfg(any(feature = "pedersen381", feature = "pedersen761"))]
+    #[cfg(any(
+        feature = "pedersen254",
+        feature = "pedersen377",
+        feature = "pedersen381",
+        feature = "pedersen761",
+        feature = "poseidon381"
+    ))]
     exec_marlin(16);
 }
 
-#[cfg(all(feature = "cli", any(feature = "pedersen381", feature = "pedersen761")))]
+#[cfg(all(
+    feature = "cli",
+    any(
+        feature = "pedersen254",
+        feature = "pedersen377",
+        feature = "pedersen381",
+        feature = "pedersen761",
+        feature = "poseidon381"
+    )
+))]
 fn exec_cli() {
     let mut members: Box<Vec<cli::Member>> = Box::new(Vec::<cli::Member>::new());
 
@@ -94,7 +168,7 @@ fn exec_cli() {
 
                 let mut leaves = members
                     .iter()
-                    .map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+                    .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
                     .collect::<Vec<_>>();
                 let tree = new_membership_tree(
                     &leaf_crh_params,
@@ -119,7 +193,7 @@ fn exec_cli() {
                 if let Some(index) = members.iter().position(|member| member.id == id) {
                     let mut leaves = members
                         .iter()
-                        .map(|member| member.hash::<LeafHash>(&leaf_crh_params))
+                        .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
                         .collect::<Vec<_>>();
                     let tree = new_membership_tree(
                         &leaf_crh_params,
@@ -135,10 +209,12 @@ fn exec_cli() {
                         leaf_crh_params: leaf_crh_params.clone(),
                         two_to_one_crh_params: two_to_one_crh_params.clone(),
                         root,
-                        leaf_hash: member.hash::<LeafHash>(&leaf_crh_params),
+                        leaf_hash: member.hash::<LeafHash>(&leaf_crh_params).unwrap(),
                         authentication_path: Some(path),
                     };
 
+                    // Keys from the `ceremony` binary are not loaded here: the CLI
+                    // still sets up per proof, for trees sized by member count
                     let (pk, vk) =
                         Groth16::<Curve>::circuit_specific_setup(circuit.clone(), &mut rng)
                             .unwrap();
@@ -147,6 +223,7 @@ fn exec_cli() {
                     let mut leaf_hash_serialization = Vec::new();
                     member
                         .hash::<LeafHash>(&leaf_crh_params)
+                        .unwrap()
                         .serialize(&mut leaf_hash_serialization)
                         .unwrap();
                     println!(
@@ -176,7 +253,8 @@ fn exec_cli() {
                     );
 
                     // Construct public input vector properly
-                    let public_input = vec![root, member.hash::<LeafHash>(&leaf_crh_params)];
+                    let public_input =
+                        vec![root, member.hash::<LeafHash>(&leaf_crh_params).unwrap()];
 
                     // Verify the proof with proper error handling
                     match Groth16::<Curve>::verify(&vk, &public_input, &proof) {
@@ -0,0 +1,402 @@
+//! Marlin for membership circuits, generic over its polynomial commitment
+//! scheme: [`KzgPC`] (Marlin's variant of KZG) or [`SonicKzgPC`]. The two differ
+//! in key and proof sizes and in verifier cost.
+//!
+//! Marlin's setup is universal: one [`MembershipSrs`] sized for the deepest
+//! tree an application supports indexes the circuit of every shallower tree,
+//! so adding members never calls for a new setup until that bound is reached.
+
+use crate::commitments::pedersen::{LeafHash, MerkleTreeCircuit, PedersenCurve, TwoToOneHash};
+use crate::powers_of_tau::PowersOfTau;
+use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+use ark_marlin::{ahp::indexer::IndexInfo, AHPForR1CS, Marlin};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::{marlin_pc::MarlinKZG10, sonic_pc::SonicKZG10, PolynomialCommitment};
+use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::rand::RngCore;
+use blake2::Blake2s;
+
+/// Marlin's KZG polynomial commitments over `E`.
+pub type KzgPC<E> = MarlinKZG10<E, DensePolynomial<<E as PairingEngine>::Fr>>;
+
+/// Sonic's KZG polynomial commitments over `E`.
+pub type SonicKzgPC<E> = SonicKZG10<E, DensePolynomial<<E as PairingEngine>::Fr>>;
+
+/// Marlin over the circuit field `F` with commitment scheme `PC`, and Blake2s
+/// for Fiat-Shamir.
+pub type MarlinPC<F, PC> = Marlin<F, PC, Blake2s>;
+
+/// Marlin with [`KzgPC`] commitments over `E`.
+pub type MarlinKzg<E> = MarlinPC<<E as PairingEngine>::Fr, KzgPC<E>>;
+
+/// Marlin with [`SonicKzgPC`] commitments over `E`.
+pub type MarlinSonicKzg<E> = MarlinPC<<E as PairingEngine>::Fr, SonicKzgPC<E>>;
+
+pub type UniversalSrs<F, PC> = ark_marlin::UniversalSRS<F, PC>;
+pub type ProvingKey<F, PC> = ark_marlin::IndexProverKey<F, PC>;
+pub type VerifyingKey<F, PC> = ark_marlin::IndexVerifierKey<F, PC>;
+pub type Proof<F, PC> = ark_marlin::Proof<F, PC>;
+pub type Error<F, PC> =
+    ark_marlin::Error<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Error>;
+
+/// Proving and verifying key of one circuit.
+type KeyPair<F, PC> = (ProvingKey<F, PC>, VerifyingKey<F, PC>);
+
+/// Size of a circuit's index, which bounds the universal SRS able to index it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IndexBound {
+    pub num_constraints: usize,
+    pub num_variables: usize,
+    pub num_non_zero: usize,
+}
+
+impl IndexBound {
+    /// Exact index size of `circuit`, as computed by Marlin's indexer.
+    pub fn of<F: PrimeField, C: ConstraintSynthesizer<F>>(
+        circuit: C,
+    ) -> Result<Self, ark_marlin::ahp::Error> {
+        Ok(Self::from_info(
+            &AHPForR1CS::<F>::index(circuit)?.index_info,
+        ))
+    }
+
+    fn from_info<F: PrimeField>(info: &IndexInfo<F>) -> Self {
+        Self {
+            num_constraints: info.num_constraints,
+            num_variables: info.num_variables,
+            num_non_zero: info.num_non_zero,
+        }
+    }
+
+    /// Whether an SRS sized for `self` can index a circuit sized `other`.
+    pub fn covers(&self, other: &Self) -> bool {
+        self.num_constraints >= other.num_constraints
+            && self.num_variables >= other.num_variables
+            && self.num_non_zero >= other.num_non_zero
+    }
+}
+
+/// Universal SRS shared by all membership circuits within its [`IndexBound`].
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MembershipSrs<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> {
+    srs: UniversalSrs<F, PC>,
+    bound: IndexBound,
+}
+
+impl<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> MembershipSrs<F, PC> {
+    /// Samples an SRS for circuits within `bound`.
+    pub fn setup<R: RngCore>(bound: IndexBound, rng: &mut R) -> Result<Self, Error<F, PC>> {
+        let srs = MarlinPC::<F, PC>::universal_setup(
+            bound.num_constraints,
+            bound.num_variables,
+            bound.num_non_zero,
+            rng,
+        )?;
+        Ok(Self { srs, bound })
+    }
+
+    /// Samples an SRS sized exactly for `circuit`, e.g. the membership circuit
+    /// of the largest tree the application supports.
+    pub fn for_circuit<C: ConstraintSynthesizer<F>, R: RngCore>(
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Self, Error<F, PC>> {
+        Self::setup(IndexBound::of(circuit)?, rng)
+    }
+
+    pub fn bound(&self) -> IndexBound {
+        self.bound
+    }
+
+    pub fn srs(&self) -> &UniversalSrs<F, PC> {
+        &self.srs
+    }
+
+    /// Derives the keys of `circuit`, which must fit within [`Self::bound`].
+    ///
+    /// The SRS is rounded up to a power-of-two degree, so Marlin alone would
+    /// accept some larger circuits; they are rejected so that the bound an SRS
+    /// was set up for is the one it is held to.
+    pub fn index<C: ConstraintSynthesizer<F>>(
+        &self,
+        circuit: C,
+    ) -> Result<KeyPair<F, PC>, Error<F, PC>> {
+        let (pk, vk) = MarlinPC::<F, PC>::index(&self.srs, circuit)?;
+        if !self.bound.covers(&IndexBound::from_info(&vk.index_info)) {
+            return Err(ark_marlin::Error::IndexTooLarge);
+        }
+        Ok((pk, vk))
+    }
+}
+
+impl<E: PairingEngine> MembershipSrs<E::Fr, KzgPC<E>> {
+    /// Takes the SRS for circuits within `bound` from a verified powers-of-tau
+    /// transcript rather than sampling it, so that nobody involved in the
+    /// application knows its trapdoor.
+    pub fn from_powers_of_tau(
+        transcript: &PowersOfTau<E>,
+        bound: IndexBound,
+    ) -> Result<Self, Error<E::Fr, KzgPC<E>>> {
+        let max_degree = AHPForR1CS::<E::Fr>::max_degree(
+            bound.num_constraints,
+            bound.num_variables,
+            bound.num_non_zero,
+        )?;
+        let srs = transcript.universal_params(max_degree).map_err(|_| {
+            ark_marlin::Error::PolynomialCommitmentError(
+                ark_poly_commit::Error::TrimmingDegreeTooLarge,
+            )
+        })?;
+        Ok(Self { srs, bound })
+    }
+}
+
+impl<E: PairingEngine> MembershipSrs<E::Fr, SonicKzgPC<E>> {
+    /// Always fails: Sonic's KZG enforces Marlin's degree bounds with powers
+    /// `τ^-i · G2`, which a powers-of-tau transcript does not hold. Taking the
+    /// SRS without them would only fail once a circuit is indexed.
+    pub fn from_powers_of_tau(
+        _transcript: &PowersOfTau<E>,
+        bound: IndexBound,
+    ) -> Result<Self, Error<E::Fr, SonicKzgPC<E>>> {
+        // The smaller of the two bounds Marlin enforces
+        let domain_h_size =
+            GeneralEvaluationDomain::<E::Fr>::compute_size_of_domain(bound.num_constraints).ok_or(
+                ark_marlin::ahp::Error::from(SynthesisError::PolynomialDegreeTooLarge),
+            )?;
+        Err(ark_marlin::Error::PolynomialCommitmentError(
+            ark_poly_commit::Error::UnsupportedDegreeBound(domain_h_size - 2),
+        ))
+    }
+}
+
+/// Depth of the tree [`new_membership_tree`](crate::commitments::pedersen::new_membership_tree)
+/// builds for `num_members` members: leaves are padded to a power of two, and
+/// to at least two.
+pub fn tree_depth(num_members: usize) -> usize {
+    num_members.max(2).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Samples an SRS for the [`MerkleTreeCircuit`] of every Pedersen tree up to
+/// `max_depth`. The circuit's size depends only on the tree depth, so it is
+/// measured on a placeholder path.
+pub fn setup_for_depth<C, PC, R>(
+    leaf_crh_params: &<LeafHash<C> as CRH>::Parameters,
+    two_to_one_crh_params: &<TwoToOneHash<C> as TwoToOneCRH>::Parameters,
+    max_depth: usize,
+    rng: &mut R,
+) -> Result<MembershipSrs<C::Field, PC>, Error<C::Field, PC>>
+where
+    C: PedersenCurve,
+    PC: PolynomialCommitment<C::Field, DensePolynomial<C::Field>>,
+    R: RngCore,
+{
+    let circuit = MerkleTreeCircuit::<C>::placeholder(
+        leaf_crh_params.clone(),
+        two_to_one_crh_params.clone(),
+        max_depth,
+    );
+    let bound = IndexBound::of::<C::Field, _>(circuit)?;
+    MembershipSrs::setup(bound, rng)
+}
+
+pub fn prove<F, PC, C, R>(
+    pk: &ProvingKey<F, PC>,
+    circuit: C,
+    rng: &mut R,
+) -> Result<Proof<F, PC>, Error<F, PC>>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DensePolynomial<F>>,
+    C: ConstraintSynthesizer<F>,
+    R: RngCore,
+{
+    MarlinPC::<F, PC>::prove(pk, circuit, rng)
+}
+
+/// Verifies `proof` against the circuit's public inputs, in allocation order.
+/// `rng` randomizes the batched pairing checks.
+pub fn verify<F, PC, R>(
+    vk: &VerifyingKey<F, PC>,
+    public_inputs: &[F],
+    proof: &Proof<F, PC>,
+    rng: &mut R,
+) -> Result<bool, Error<F, PC>>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DensePolynomial<F>>,
+    R: RngCore,
+{
+    MarlinPC::<F, PC>::verify(vk, public_inputs, proof, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::pedersen::new_membership_tree;
+    use crate::commitments::pedersen381::EdOnBls12_381;
+    use crate::member::{generate_members, Member};
+    use crate::powers_of_tau::tests::sample;
+    use ark_bls12_381::{Bls12_381, Fr};
+
+    type Curve = EdOnBls12_381;
+
+    #[test]
+    fn tree_depths() {
+        assert_eq!(tree_depth(1), 1);
+        assert_eq!(tree_depth(2), 1);
+        assert_eq!(tree_depth(3), 2);
+        assert_eq!(tree_depth(8), 3);
+        assert_eq!(tree_depth(9), 4);
+    }
+
+    #[test]
+    fn one_srs_for_all_tree_sizes() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<Curve> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<Curve> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let srs = setup_for_depth::<Curve, KzgPC<Bls12_381>, _>(
+            &leaf_crh_params,
+            &two_to_one_crh_params,
+            3,
+            &mut rng,
+        )
+        .unwrap();
+
+        // The SRS survives a round trip through its encoding
+        let mut bytes = Vec::new();
+        srs.serialize(&mut bytes).unwrap();
+        let srs = MembershipSrs::<Fr, KzgPC<Bls12_381>>::deserialize(&*bytes).unwrap();
+
+        let mut members: Box<Vec<Member>> = Box::default();
+        generate_members(&mut members, 16);
+
+        for num_members in [2, 3, 8, 16] {
+            let mut leaves = members[..num_members]
+                .iter()
+                .map(|member| member.hash::<LeafHash<Curve>>(&leaf_crh_params).unwrap())
+                .collect::<Vec<_>>();
+            let tree =
+                new_membership_tree::<Curve>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+            let circuit = MerkleTreeCircuit::<Curve> {
+                leaf_crh_params: leaf_crh_params.clone(),
+                two_to_one_crh_params: two_to_one_crh_params.clone(),
+                root: tree.root(),
+                leaf_hash: leaves[1],
+                authentication_path: Some(tree.generate_proof(1).unwrap()),
+            };
+
+            if tree_depth(num_members) > 3 {
+                assert!(!srs
+                    .bound()
+                    .covers(&IndexBound::of(circuit.clone()).unwrap()));
+                assert!(srs.index(circuit).is_err());
+                continue;
+            }
+
+            let (pk, vk) = srs.index(circuit.clone()).unwrap();
+            let proof = prove(&pk, circuit, &mut rng).unwrap();
+            assert!(verify(&vk, &[tree.root(), leaves[1]], &proof, &mut rng).unwrap());
+        }
+    }
+
+    /// Proves membership in a two-member tree with the commitment scheme `PC`.
+    fn prove_and_verify<PC: PolynomialCommitment<Fr, DensePolynomial<Fr>>>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<Curve> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<Curve> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<Curve>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree =
+            new_membership_tree::<Curve>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        let circuit = MerkleTreeCircuit::<Curve> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: tree.root(),
+            leaf_hash: leaves[0],
+            authentication_path: Some(tree.generate_proof(0).unwrap()),
+        };
+
+        let srs = MembershipSrs::<Fr, PC>::for_circuit(circuit.clone(), &mut rng).unwrap();
+        let (pk, vk) = srs.index(circuit.clone()).unwrap();
+        let proof = prove(&pk, circuit, &mut rng).unwrap();
+
+        assert!(verify(&vk, &[tree.root(), leaves[0]], &proof, &mut rng).unwrap());
+        assert!(!verify(&vk, &[tree.root(), leaves[1]], &proof, &mut rng).unwrap());
+    }
+
+    #[test]
+    fn both_commitment_schemes() {
+        prove_and_verify::<KzgPC<Bls12_381>>();
+        prove_and_verify::<SonicKzgPC<Bls12_381>>();
+    }
+
+    #[test]
+    fn srs_from_powers_of_tau() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash<Curve> as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash<Curve> as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash<Curve>>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree =
+            new_membership_tree::<Curve>(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+
+        let circuit = MerkleTreeCircuit::<Curve> {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root: tree.root(),
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+        let bound = IndexBound::of(circuit.clone()).unwrap();
+        let max_degree = AHPForR1CS::<Fr>::max_degree(
+            bound.num_constraints,
+            bound.num_variables,
+            bound.num_non_zero,
+        )
+        .unwrap();
+
+        // A transcript one power short cannot back the SRS
+        let transcript = sample::<Bls12_381, _>(max_degree, &mut rng);
+        assert!(
+            MembershipSrs::<Fr, KzgPC<Bls12_381>>::from_powers_of_tau(&transcript, bound).is_err()
+        );
+
+        let transcript = sample::<Bls12_381, _>(max_degree + 1, &mut rng);
+        assert!(matches!(
+            MembershipSrs::<Fr, SonicKzgPC<Bls12_381>>::from_powers_of_tau(&transcript, bound),
+            Err(ark_marlin::Error::PolynomialCommitmentError(
+                ark_poly_commit::Error::UnsupportedDegreeBound(_)
+            ))
+        ));
+        let srs =
+            MembershipSrs::<Fr, KzgPC<Bls12_381>>::from_powers_of_tau(&transcript, bound).unwrap();
+        let (pk, vk) = srs.index(circuit.clone()).unwrap();
+        let proof = prove(&pk, circuit, &mut rng).unwrap();
+
+        assert!(verify(&vk, &[tree.root(), leaves[1]], &proof, &mut rng).unwrap());
+    }
+}
@@ -1,11 +1,129 @@
+use crate::crh::MaxInput;
 use ark_crypto_primitives::CRH;
 use chrono::{DateTime, Utc};
+use core::fmt;
 use serde::ser::SerializeStruct;
 
+/// Length of the secret of an [`Identity`].
+pub const MEMBER_SECRET_BYTES: usize = 16;
+
+/// Length of the identity commitment slot in a member record, wide enough for
+/// a serialized element of every supported field.
+pub const IDENTITY_COMMITMENT_BYTES: usize = 48;
+
+/// Length of a big-endian UNIX timestamp in a member record.
+pub const TIMESTAMP_BYTES: usize = 8;
+
+/// Offset of the byte telling whether the member record has an identity
+/// commitment.
+pub const IDENTITY_COMMITMENT_FLAG_OFFSET: usize = 0;
+
+/// Offset of the identity commitment in [`Member::to_bytes`], zeroed when absent.
+pub const IDENTITY_COMMITMENT_OFFSET: usize = IDENTITY_COMMITMENT_FLAG_OFFSET + 1;
+
+/// Offset of the join date in [`Member::to_bytes`].
+pub const JOIN_DATE_OFFSET: usize = IDENTITY_COMMITMENT_OFFSET + IDENTITY_COMMITMENT_BYTES;
+
+/// Offset of the byte telling whether the member record has an end date.
+pub const END_DATE_FLAG_OFFSET: usize = JOIN_DATE_OFFSET + TIMESTAMP_BYTES;
+
+/// Offset of the end date in [`Member::to_bytes`].
+pub const END_DATE_OFFSET: usize = END_DATE_FLAG_OFFSET + 1;
+
+/// Offset of the byte holding the length of the id.
+pub const ID_LEN_OFFSET: usize = END_DATE_OFFSET + TIMESTAMP_BYTES;
+
+/// Offset of the byte holding the length of the email.
+pub const EMAIL_LEN_OFFSET: usize = ID_LEN_OFFSET + 1;
+
+/// Offset of the id in [`Member::to_bytes`], immediately followed by the email.
+pub const ID_OFFSET: usize = EMAIL_LEN_OFFSET + 1;
+
+/// Error returned when a member record cannot be serialized or hashed.
+#[derive(Debug)]
+pub enum MemberError {
+    /// The id is longer than the 255 bytes its length byte can describe.
+    IdTooLong(usize),
+    /// The email is longer than the 255 bytes its length byte can describe.
+    EmailTooLong(usize),
+    /// The serialized record is longer than the leaf hash can absorb.
+    RecordTooLong { length: usize, max: usize },
+    /// The serialized identity commitment does not fit its slot in the record.
+    CommitmentTooLong(usize),
+    /// The leaf hash failed on the record.
+    Hash(ark_crypto_primitives::Error),
+}
+
+impl fmt::Display for MemberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IdTooLong(length) => write!(f, "member id is {} bytes long, at most 255", length),
+            Self::EmailTooLong(length) => {
+                write!(f, "member email is {} bytes long, at most 255", length)
+            }
+            Self::RecordTooLong { length, max } => write!(
+                f,
+                "member record is {} bytes long, the leaf hash absorbs at most {}",
+                length, max
+            ),
+            Self::CommitmentTooLong(length) => write!(
+                f,
+                "identity commitment is {} bytes long, at most {}",
+                length, IDENTITY_COMMITMENT_BYTES
+            ),
+            Self::Hash(err) => write!(f, "could not hash member record: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MemberError {}
+
+/// Secret a member keeps to themselves to derive nullifiers.
+///
+/// Only its commitment goes into the [`Member`] record the registrar stores
+/// and hashes into the tree, so the registrar cannot derive the member's
+/// nullifiers; a proof shows knowledge of the secret by opening the commitment.
+#[derive(Clone)]
+pub struct Identity {
+    secret: [u8; MEMBER_SECRET_BYTES],
+}
+
+impl Identity {
+    pub fn random() -> Self {
+        Self::from_secret(rand::random())
+    }
+
+    pub fn from_secret(secret: [u8; MEMBER_SECRET_BYTES]) -> Self {
+        Self { secret }
+    }
+
+    pub fn secret(&self) -> &[u8; MEMBER_SECRET_BYTES] {
+        &self.secret
+    }
+
+    /// The commitment `H(secret)`, serialized and zero-padded to the slot it
+    /// takes in a member record.
+    pub fn commitment<H: CRH>(
+        &self,
+        params: &H::Parameters,
+    ) -> Result<[u8; IDENTITY_COMMITMENT_BYTES], MemberError> {
+        let output = H::evaluate(params, &self.secret).map_err(MemberError::Hash)?;
+        let bytes = ark_ff::to_bytes![output].map_err(|err| MemberError::Hash(err.into()))?;
+        if bytes.len() > IDENTITY_COMMITMENT_BYTES {
+            return Err(MemberError::CommitmentTooLong(bytes.len()));
+        }
+
+        let mut commitment = [0; IDENTITY_COMMITMENT_BYTES];
+        commitment[..bytes.len()].copy_from_slice(&bytes);
+        Ok(commitment)
+    }
+}
+
 #[derive(Clone)]
 pub struct Member {
     // private fields
     pub id: String,
+    identity_commitment: Option<[u8; IDENTITY_COMMITMENT_BYTES]>,
     email: String,
     join_date: DateTime<Utc>,
     end_date: Option<DateTime<Utc>>,
@@ -16,7 +134,10 @@ use ark_std::io::{Result as IoResult, Write};
 
 impl ark_ff::bytes::ToBytes for Member {
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
-        Ok((writer.write_all(&self.to_bytes()))?)
+        let bytes = self.to_bytes().map_err(|err| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string())
+        })?;
+        writer.write_all(&bytes)
     }
 }
 
@@ -24,6 +145,7 @@ impl Default for Member {
     fn default() -> Self {
         Member {
             id: "".to_string(),
+            identity_commitment: None,
             email: "".to_string(),
             join_date: chrono::offset::Utc::now(),
             end_date: None,
@@ -36,6 +158,7 @@ impl Member {
     pub fn new(id: String, email: String, end_date: Option<DateTime<Utc>>) -> Self {
         Self {
             id,
+            identity_commitment: None,
             email,
             join_date: chrono::offset::Utc::now(),
             end_date,
@@ -43,6 +166,22 @@ impl Member {
         }
     }
 
+    pub fn new_with_join_date(
+        id: String,
+        email: String,
+        join_date: DateTime<Utc>,
+        end_date: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            identity_commitment: None,
+            email,
+            join_date,
+            end_date,
+            padding: None,
+        }
+    }
+
     pub fn new_with_padding(
         id: String,
         email: String,
@@ -51,6 +190,7 @@ impl Member {
     ) -> Self {
         Self {
             id,
+            identity_commitment: None,
             email,
             join_date: chrono::offset::Utc::now(),
             end_date,
@@ -58,11 +198,52 @@ impl Member {
         }
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    /// Whether the email address is `<local part>@<domain>`.
+    pub fn has_email_domain(&self, domain: &str) -> bool {
+        self.email
+            .strip_suffix(domain)
+            .is_some_and(|local| local.ends_with('@'))
+    }
+
+    /// Attaches the commitment of the member's [`Identity`], which nullifier
+    /// proofs open. Records without one can still prove plain membership.
+    pub fn with_identity_commitment(mut self, commitment: [u8; IDENTITY_COMMITMENT_BYTES]) -> Self {
+        self.identity_commitment = Some(commitment);
+        self
+    }
+
+    pub fn identity_commitment(&self) -> Option<&[u8; IDENTITY_COMMITMENT_BYTES]> {
+        self.identity_commitment.as_ref()
+    }
+
+    /// Serializes the member record.
+    ///
+    /// The fixed-width fields come first so circuits can read them at fixed
+    /// offsets: the identity commitment flag and commitment, the join date, the
+    /// end date flag and the end date (zeroed when absent), see
+    /// [`JOIN_DATE_OFFSET`] and friends. They are
+    /// followed by the lengths of the id and the email, so the variable-length
+    /// fields can be located inside circuits too, and are why ids and emails are
+    /// limited to 255 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MemberError> {
+        let id_len =
+            u8::try_from(self.id.len()).map_err(|_| MemberError::IdTooLong(self.id.len()))?;
+        let email_len = u8::try_from(self.email.len())
+            .map_err(|_| MemberError::EmailTooLong(self.email.len()))?;
+
         let mut bytes = Vec::<u8>::new();
 
-        bytes.extend_from_slice(self.id.as_bytes());
-        bytes.extend_from_slice(self.email.as_bytes());
+        if let Some(commitment) = &self.identity_commitment {
+            bytes.extend_from_slice(&[1_u8]);
+            bytes.extend_from_slice(commitment);
+        } else {
+            bytes.extend_from_slice(&[0_u8]);
+            bytes.extend_from_slice(&[0_u8; IDENTITY_COMMITMENT_BYTES]);
+        }
         bytes.extend_from_slice(&self.join_date.timestamp().to_be_bytes());
 
         if let Some(end_date) = self.end_date {
@@ -70,20 +251,44 @@ impl Member {
             bytes.extend_from_slice(&end_date.timestamp().to_be_bytes());
         } else {
             bytes.extend_from_slice(&[0_u8]);
+            bytes.extend_from_slice(&[0_u8; TIMESTAMP_BYTES]);
         }
 
+        bytes.push(id_len);
+        bytes.push(email_len);
+        bytes.extend_from_slice(self.id.as_bytes());
+        bytes.extend_from_slice(self.email.as_bytes());
+
         if let Some(padding) = &self.padding {
             bytes.extend_from_slice(&[1_u8]);
-            bytes.extend_from_slice(&padding); // does this work???
+            bytes.extend_from_slice(padding); // does this work???
         } else {
             bytes.extend_from_slice(&[0_u8]);
         }
 
-        bytes
+        Ok(bytes)
     }
 
-    pub fn hash<H: CRH>(&self, params: &H::Parameters) -> H::Output {
-        H::evaluate(params, &self.to_bytes()).unwrap()
+    /// Whether the membership is active at `now`: the member joined at or before
+    /// `now` and the membership has not ended yet.
+    pub fn is_active_at(&self, now: &DateTime<Utc>) -> bool {
+        self.join_date <= *now && self.end_date.is_none_or(|end_date| *now < end_date)
+    }
+
+    /// Hashes the serialized record into a leaf. Records longer than
+    /// `H::MAX_INPUT_BYTES` are rejected rather than passed to `H`, whose
+    /// windowed hashes panic on them.
+    pub fn hash<H: MaxInput>(&self, params: &H::Parameters) -> Result<H::Output, MemberError> {
+        let bytes = self.to_bytes()?;
+        let max = H::MAX_INPUT_BYTES;
+        if bytes.len() > max {
+            return Err(MemberError::RecordTooLong {
+                length: bytes.len(),
+                max,
+            });
+        }
+
+        H::evaluate(params, &bytes).map_err(MemberError::Hash)
     }
 }
 
@@ -92,8 +297,12 @@ impl serde::Serialize for Member {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("Member", 4)?;
+        let mut state = serializer.serialize_struct("Member", 6)?;
         state.serialize_field("id", &self.id)?;
+        state.serialize_field(
+            "identity_commitment",
+            &self.identity_commitment.map(hex::encode),
+        )?;
         state.serialize_field("email", &self.email)?;
         state.serialize_field("join_date", &self.join_date.to_rfc3339())?;
         state.serialize_field("end_date", &self.end_date.map(|d| d.to_rfc3339()))?;
@@ -113,6 +322,7 @@ impl<'de> serde::Deserialize<'de> for Member {
         #[derive(serde::Deserialize)]
         struct MemberData {
             id: String,
+            identity_commitment: Option<String>,
             email: String,
             join_date: String,
             end_date: Option<String>,
@@ -120,6 +330,15 @@ impl<'de> serde::Deserialize<'de> for Member {
         }
 
         let data = MemberData::deserialize(deserializer)?;
+        let identity_commitment = match data.identity_commitment {
+            Some(commitment) => Some(
+                hex::decode(commitment)
+                    .map_err(serde::de::Error::custom)?
+                    .try_into()
+                    .map_err(|_| serde::de::Error::custom("invalid identity commitment length"))?,
+            ),
+            _ => None,
+        };
         let join_date = DateTime::parse_from_rfc3339(&data.join_date)
             .map_err(serde::de::Error::custom)?
             .with_timezone(&Utc);
@@ -132,15 +351,13 @@ impl<'de> serde::Deserialize<'de> for Member {
             _ => None,
         };
 
-        let padding = match data.padding {
-            Some(pad) => Some(
-                vec![0; pad.len()], // why does Vec<u8>! not work
-            ),
-            _ => None,
-        }; // padding deserialized
+        let padding = data.padding.map(
+            |pad| vec![0; pad.len()], // why does Vec<u8>! not work
+        ); // padding deserialized
 
         Ok(Member {
             id: data.id,
+            identity_commitment,
             email: data.email,
             join_date,
             end_date,
@@ -174,13 +391,129 @@ mod tests {
     #[test]
     fn test_serialization() {
         let member = Member::default();
-        let bytes = member.to_bytes();
+        let bytes = member.to_bytes().unwrap();
         println!("Member serialized to {} bytes: {:?}", bytes.len(), bytes);
 
         // Verify that serialization includes all fields
         assert!(bytes.len() > member.id.len() + member.email.len());
     }
 
+    #[test]
+    fn test_identity_commitment_layout() {
+        use crate::commitments::pedersen381::common::LeafHash;
+
+        let params = <LeafHash as CRH>::setup(&mut ark_std::test_rng()).unwrap();
+        let identity = Identity::random();
+        let commitment = identity.commitment::<LeafHash>(&params).unwrap();
+
+        let member = Member::default();
+        let bytes = member.to_bytes().unwrap();
+        assert_eq!(bytes[IDENTITY_COMMITMENT_FLAG_OFFSET], 0);
+        assert_eq!(
+            &bytes[IDENTITY_COMMITMENT_OFFSET..JOIN_DATE_OFFSET],
+            &[0; IDENTITY_COMMITMENT_BYTES]
+        );
+
+        let member = member.with_identity_commitment(commitment);
+        let bytes = member.to_bytes().unwrap();
+        assert_eq!(bytes[IDENTITY_COMMITMENT_FLAG_OFFSET], 1);
+        assert_eq!(
+            &bytes[IDENTITY_COMMITMENT_OFFSET..JOIN_DATE_OFFSET],
+            &commitment
+        );
+
+        // The record never contains the secret itself
+        assert!(!bytes
+            .windows(MEMBER_SECRET_BYTES)
+            .any(|window| window == identity.secret()));
+    }
+
+    #[test]
+    fn test_timestamp_layout() {
+        let join_date = DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end_date = DateTime::parse_from_rfc3339("2023-12-31T23:59:59Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let member =
+            Member::new_with_join_date("1".into(), "1@usc.edu".into(), join_date, Some(end_date));
+        let bytes = member.to_bytes().unwrap();
+        let timestamp_at = |bytes: &[u8], offset: usize| {
+            i64::from_be_bytes(bytes[offset..offset + TIMESTAMP_BYTES].try_into().unwrap())
+        };
+
+        assert_eq!(
+            timestamp_at(&bytes, JOIN_DATE_OFFSET),
+            join_date.timestamp()
+        );
+        assert_eq!(bytes[END_DATE_FLAG_OFFSET], 1);
+        assert_eq!(timestamp_at(&bytes, END_DATE_OFFSET), end_date.timestamp());
+
+        // Records without an end date keep the same layout
+        let member = Member::new_with_join_date("1".into(), "1@usc.edu".into(), join_date, None);
+        let bytes = member.to_bytes().unwrap();
+        assert_eq!(bytes[END_DATE_FLAG_OFFSET], 0);
+        assert_eq!(timestamp_at(&bytes, END_DATE_OFFSET), 0);
+    }
+
+    #[test]
+    fn test_field_lengths() {
+        let member = Member::new("1".repeat(255), "1@usc.edu".into(), None);
+        assert!(member.to_bytes().is_ok());
+
+        let member = Member::new("1".repeat(256), "1@usc.edu".into(), None);
+        assert!(matches!(
+            member.to_bytes(),
+            Err(MemberError::IdTooLong(256))
+        ));
+
+        let member = Member::new("1".into(), format!("{}@usc.edu", "1".repeat(256)), None);
+        assert!(matches!(
+            member.to_bytes(),
+            Err(MemberError::EmailTooLong(264))
+        ));
+    }
+
+    #[test]
+    fn test_email_layout() {
+        let member = Member::new("42".into(), "42@usc.edu".into(), None);
+        let bytes = member.to_bytes().unwrap();
+        let email_offset = ID_OFFSET + bytes[ID_LEN_OFFSET] as usize;
+
+        assert_eq!(&bytes[ID_OFFSET..email_offset], b"42");
+        assert_eq!(
+            &bytes[email_offset..email_offset + bytes[EMAIL_LEN_OFFSET] as usize],
+            b"42@usc.edu"
+        );
+
+        assert!(member.has_email_domain("usc.edu"));
+        assert!(!member.has_email_domain("sc.edu"));
+        assert!(!member.has_email_domain("ucla.edu"));
+    }
+
+    #[test]
+    fn test_is_active_at() {
+        let at = |date: &str| {
+            DateTime::parse_from_rfc3339(date)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        let member = Member::new_with_join_date(
+            "1".into(),
+            "1@usc.edu".into(),
+            at("2023-01-01T00:00:00Z"),
+            Some(at("2024-01-01T00:00:00Z")),
+        );
+
+        assert!(!member.is_active_at(&at("2022-12-31T23:59:59Z")));
+        assert!(member.is_active_at(&at("2023-01-01T00:00:00Z")));
+        assert!(member.is_active_at(&at("2023-12-31T23:59:59Z")));
+        assert!(!member.is_active_at(&at("2024-01-01T00:00:00Z")));
+    }
+
     #[test]
     fn test_serde_serialization() {
         let member = Member::default();
@@ -233,13 +566,15 @@ mod tests {
                     .unwrap()
                     .with_timezone(&Utc),
             ),
-        );
+        )
+        .with_identity_commitment([7; IDENTITY_COMMITMENT_BYTES]);
 
         let serialized = serde_json::to_string(&member).expect("Failed to serialize Member");
         let deserialized: Member =
             serde_json::from_str(&serialized).expect("Failed to deserialize Member");
 
         assert_eq!(member.id, deserialized.id);
+        assert_eq!(member.identity_commitment, deserialized.identity_commitment);
         assert_eq!(member.email, deserialized.email);
         assert_eq!(member.join_date, deserialized.join_date);
         assert_eq!(member.end_date, deserialized.end_date);
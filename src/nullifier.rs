@@ -0,0 +1,95 @@
+use ark_ff::Field;
+use std::collections::HashSet;
+
+/// Keeps track of the nullifiers that were already accepted, so each member can
+/// only prove once per external nullifier (e.g. once per poll or per epoch).
+#[derive(Clone, Debug, Default)]
+pub struct NullifierStore<F: Field> {
+    seen: HashSet<(F, F)>,
+}
+
+impl<F: Field> NullifierStore<F> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashSet::new(),
+        }
+    }
+
+    pub fn contains(&self, external_nullifier: &F, nullifier: &F) -> bool {
+        self.seen.contains(&(*external_nullifier, *nullifier))
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Runs `verify` and records `nullifier` if the proof is valid.
+    ///
+    /// A nullifier that was already recorded for `external_nullifier` is rejected
+    /// with `Ok(false)` without running `verify`; a proof that fails to verify
+    /// does not consume its nullifier.
+    pub fn verify_and_insert<E>(
+        &mut self,
+        external_nullifier: F,
+        nullifier: F,
+        verify: impl FnOnce() -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        if self.contains(&external_nullifier, &nullifier) {
+            return Ok(false);
+        }
+
+        let verified = verify()?;
+        if verified {
+            self.seen.insert((external_nullifier, nullifier));
+        }
+        Ok(verified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NullifierStore;
+    use ark_bls12_381::Fr;
+
+    #[test]
+    fn rejects_repeated_nullifiers() {
+        let mut store = NullifierStore::<Fr>::new();
+        let (poll, nullifier) = (Fr::from(1u64), Fr::from(42u64));
+
+        assert_eq!(
+            store.verify_and_insert::<()>(poll, nullifier, || Ok(true)),
+            Ok(true)
+        );
+        assert_eq!(
+            store.verify_and_insert::<()>(poll, nullifier, || Ok(true)),
+            Ok(false)
+        );
+
+        // The same nullifier is unrelated under another external nullifier
+        assert_eq!(
+            store.verify_and_insert::<()>(Fr::from(2u64), nullifier, || Ok(true)),
+            Ok(true)
+        );
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn failed_verification_does_not_consume_nullifier() {
+        let mut store = NullifierStore::<Fr>::new();
+        let (poll, nullifier) = (Fr::from(1u64), Fr::from(42u64));
+
+        assert_eq!(
+            store.verify_and_insert::<()>(poll, nullifier, || Ok(false)),
+            Ok(false)
+        );
+        assert!(!store.contains(&poll, &nullifier));
+        assert_eq!(
+            store.verify_and_insert::<()>(poll, nullifier, || Ok(true)),
+            Ok(true)
+        );
+    }
+}
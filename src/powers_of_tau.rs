@@ -0,0 +1,723 @@
+//! Powers-of-tau transcripts, so that Marlin's KZG SRS can come from a public
+//! ceremony instead of a setup whose trapdoor is known to whoever ran it.
+//!
+//! A transcript holds, for a secret `τ` and a secret `α`:
+//!
+//! | section               | elements                  | count   |
+//! |-----------------------|---------------------------|---------|
+//! | `tau_powers_g1`       | `τ^i · G1`, `i = 0..n`    | `n ≥ 2` |
+//! | `tau_powers_g2`       | `τ^i · G2`, `i = 0..m`    | `m ≥ 2` |
+//! | `alpha_tau_powers_g1` | `α·τ^i · G1`, `i = 0..k`  | `k ≥ 3` |
+//!
+//! where `G1` and `G2` are the curves' prime-subgroup generators.
+//! [`PowersOfTau::read`] takes the sections stored one after the other, each as
+//! its count (a little-endian `u64`) followed by that many points in arkworks'
+//! compressed encoding.
+//!
+//! [`PowersOfTau::read_perpetual`] takes the `challenge` and `response` files
+//! of the perpetual powers-of-tau ceremony over BN254
+//! (<https://github.com/privacy-scaling-explorations/perpetualpowersoftau>)
+//! as they are published. For a ceremony of size `2^p`, such a file holds a
+//! 64-byte BLAKE2b hash of the previous file, then
+//!
+//! | section               | count          |
+//! |-----------------------|----------------|
+//! | `tau_powers_g1`       | `2^(p+1) - 1`  |
+//! | `tau_powers_g2`       | `2^p`          |
+//! | `alpha_tau_powers_g1` | `2^p`          |
+//! | `beta_tau_powers_g1`  | `2^p`          |
+//! | `beta_g2`             | `1`            |
+//!
+//! without counts, in bellman's encoding: coordinates as big-endian integers,
+//! those of `Fq2` elements as `c1` then `c0`, and flags in the two top bits of
+//! the first byte (`0x40` for the point at infinity, and in compressed points
+//! `0x80` for the larger of the two `y`). `challenge` files are uncompressed,
+//! `response` files compressed and followed by the contributor's public key.
+//! Only the points are checked here, not the hash chain of contributions,
+//! which the ceremony's own verifier covers.
+//!
+//! `τ^i · G1` become the powers of the KZG committer key, `G2` and `τ · G2` the
+//! pairing side of the verifier key, and `α·τ^i · G1` the powers of the hiding
+//! generator (`γ` in KZG), which Marlin needs for its zero-knowledge masks.
+
+use ark_bn254::{Bn254, Fq, Fq2};
+use ark_ec::{
+    models::SWModelParameters,
+    msm::{FixedBaseMSM, VariableBaseMSM},
+    short_weierstrass_jacobian::GroupAffine,
+    AffineCurve, PairingEngine, ProjectiveCurve,
+};
+use ark_ff::{Field, FromBytes, PrimeField, UniformRand, Zero};
+use ark_poly_commit::kzg10::UniversalParams;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::rand::RngCore;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Why a transcript was rejected.
+#[derive(Debug)]
+pub enum TranscriptError {
+    /// The transcript is not encoded as described in the [module docs](self),
+    /// or holds a point outside the prime-order subgroup.
+    Serialization(SerializationError),
+    /// A section holds fewer points than a usable SRS needs.
+    TooFewPowers {
+        section: &'static str,
+        required: usize,
+        found: usize,
+    },
+    /// The first power of tau is not the generator of its group.
+    WrongGenerator,
+    /// `τ` or `α` is zero or one.
+    Degenerate,
+    /// A section is not a geometric sequence with ratio `τ`.
+    InconsistentPowers(&'static str),
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialization(err) => write!(f, "malformed transcript: {}", err),
+            Self::TooFewPowers {
+                section,
+                required,
+                found,
+            } => write!(
+                f,
+                "{} holds {} powers, at least {} are required",
+                section, found, required
+            ),
+            Self::WrongGenerator => write!(f, "transcript does not start at the generators"),
+            Self::Degenerate => write!(f, "transcript was produced with a trivial secret"),
+            Self::InconsistentPowers(section) => {
+                write!(f, "{} are not consecutive powers of tau", section)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TranscriptError {}
+
+impl From<SerializationError> for TranscriptError {
+    fn from(err: SerializationError) -> Self {
+        Self::Serialization(err)
+    }
+}
+
+/// A powers-of-tau transcript that passed [`PowersOfTau::verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowersOfTau<E: PairingEngine> {
+    tau_powers_g1: Vec<E::G1Affine>,
+    tau_powers_g2: Vec<E::G2Affine>,
+    alpha_tau_powers_g1: Vec<E::G1Affine>,
+}
+
+impl<E: PairingEngine> PowersOfTau<E> {
+    /// Reads a transcript in the layout of the [module docs](self) and verifies
+    /// it. `rng` draws the randomizers of the batched pairing checks.
+    pub fn read<R: Read, Rng: RngCore>(
+        mut reader: R,
+        rng: &mut Rng,
+    ) -> Result<Self, TranscriptError> {
+        let transcript = Self {
+            tau_powers_g1: Vec::deserialize(&mut reader)?,
+            tau_powers_g2: Vec::deserialize(&mut reader)?,
+            alpha_tau_powers_g1: Vec::deserialize(&mut reader)?,
+        };
+        transcript.verify(rng)?;
+        Ok(transcript)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.tau_powers_g1.serialize(&mut writer)?;
+        self.tau_powers_g2.serialize(&mut writer)?;
+        self.alpha_tau_powers_g1.serialize(&mut writer)
+    }
+
+    /// Largest polynomial degree the transcript can commit to and hide, which
+    /// takes one more hiding power than powers of tau.
+    pub fn max_degree(&self) -> usize {
+        (self.tau_powers_g1.len() - 1).min(self.alpha_tau_powers_g1.len() - 2)
+    }
+
+    /// Transcript for a known `τ` and `α`, with `num_g1` powers of `τ` in G1
+    /// (and one more hiding power) and `num_g2` in G2. Only for tests and local
+    /// ceremonies: whoever holds the secrets can forge proofs.
+    pub(crate) fn from_secrets(tau: E::Fr, alpha: E::Fr, num_g1: usize, num_g2: usize) -> Self {
+        let tau_powers = powers(tau, (num_g1 + 1).max(num_g2));
+        let alpha_tau_powers = tau_powers[..=num_g1]
+            .iter()
+            .map(|power| alpha * power)
+            .collect::<Vec<_>>();
+        Self {
+            tau_powers_g1: fixed_base_mul(
+                E::G1Projective::prime_subgroup_generator(),
+                &tau_powers[..num_g1],
+            ),
+            tau_powers_g2: fixed_base_mul(
+                E::G2Projective::prime_subgroup_generator(),
+                &tau_powers[..num_g2],
+            ),
+            alpha_tau_powers_g1: fixed_base_mul(
+                E::G1Projective::prime_subgroup_generator(),
+                &alpha_tau_powers,
+            ),
+        }
+    }
+
+    pub(crate) fn tau_powers_g1(&self) -> &[E::G1Affine] {
+        &self.tau_powers_g1
+    }
+
+    pub(crate) fn tau_powers_g2(&self) -> &[E::G2Affine] {
+        &self.tau_powers_g2
+    }
+
+    pub(crate) fn alpha_tau_powers_g1(&self) -> &[E::G1Affine] {
+        &self.alpha_tau_powers_g1
+    }
+
+    /// Checks that every section is a sequence of consecutive powers of the
+    /// same `τ`, starting from the generators.
+    ///
+    /// Each section is checked with one pairing equation over a random linear
+    /// combination of its consecutive pairs, e.g. for `τ^i · G1`
+    /// `e(Σ r_i·τ^(i+1)·G1, G2) = e(Σ r_i·τ^i·G1, τ·G2)`.
+    pub fn verify<R: RngCore>(&self, rng: &mut R) -> Result<(), TranscriptError> {
+        let sections = [
+            ("tau_powers_g1", self.tau_powers_g1.len(), 2),
+            ("tau_powers_g2", self.tau_powers_g2.len(), 2),
+            ("alpha_tau_powers_g1", self.alpha_tau_powers_g1.len(), 3),
+        ];
+        for (section, found, required) in sections {
+            if found < required {
+                return Err(TranscriptError::TooFewPowers {
+                    section,
+                    required,
+                    found,
+                });
+            }
+        }
+        let (g1, tau_g1) = (self.tau_powers_g1[0], self.tau_powers_g1[1]);
+        let (g2, tau_g2) = (self.tau_powers_g2[0], self.tau_powers_g2[1]);
+        if g1 != E::G1Affine::prime_subgroup_generator()
+            || g2 != E::G2Affine::prime_subgroup_generator()
+        {
+            return Err(TranscriptError::WrongGenerator);
+        }
+        // A hiding generator of known discrete log would let commitments be
+        // opened to other polynomials
+        let alpha_g1 = self.alpha_tau_powers_g1[0];
+        if tau_g1.is_zero() || tau_g1 == g1 || alpha_g1.is_zero() || alpha_g1 == g1 {
+            return Err(TranscriptError::Degenerate);
+        }
+
+        let (lhs, rhs) = same_ratio(&self.tau_powers_g1, rng);
+        if E::pairing(lhs, g2) != E::pairing(rhs, tau_g2) {
+            return Err(TranscriptError::InconsistentPowers("tau_powers_g1"));
+        }
+        let (lhs, rhs) = same_ratio(&self.tau_powers_g2, rng);
+        if E::pairing(g1, lhs) != E::pairing(tau_g1, rhs) {
+            return Err(TranscriptError::InconsistentPowers("tau_powers_g2"));
+        }
+        let (lhs, rhs) = same_ratio(&self.alpha_tau_powers_g1, rng);
+        if E::pairing(lhs, g2) != E::pairing(rhs, tau_g2) {
+            return Err(TranscriptError::InconsistentPowers("alpha_tau_powers_g1"));
+        }
+        Ok(())
+    }
+
+    /// KZG parameters for polynomials up to `max_degree`, as `MarlinKZG10`
+    /// expects them. Fails if the transcript holds fewer powers than that
+    /// degree needs.
+    ///
+    /// The transcript has no `τ^-i · G2`, so `neg_powers_of_h` is left empty:
+    /// the parameters cannot back `SonicKZG10`, which needs them to enforce
+    /// degree bounds.
+    pub fn universal_params(
+        &self,
+        max_degree: usize,
+    ) -> Result<UniversalParams<E>, TranscriptError> {
+        // KZG's own setup samples one more hiding power than powers of g
+        let sections = [
+            ("tau_powers_g1", self.tau_powers_g1.len(), max_degree + 1),
+            (
+                "alpha_tau_powers_g1",
+                self.alpha_tau_powers_g1.len(),
+                max_degree + 2,
+            ),
+        ];
+        for (section, found, required) in sections {
+            if found < required {
+                return Err(TranscriptError::TooFewPowers {
+                    section,
+                    required,
+                    found,
+                });
+            }
+        }
+
+        let h = self.tau_powers_g2[0];
+        let beta_h = self.tau_powers_g2[1];
+        Ok(UniversalParams {
+            powers_of_g: self.tau_powers_g1[..=max_degree].to_vec(),
+            powers_of_gamma_g: self.alpha_tau_powers_g1[..max_degree + 2]
+                .iter()
+                .copied()
+                .enumerate()
+                .collect(),
+            h,
+            beta_h,
+            neg_powers_of_h: BTreeMap::new(),
+            prepared_h: h.into(),
+            prepared_beta_h: beta_h.into(),
+        })
+    }
+}
+
+/// Point encoding of a perpetual powers-of-tau file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// `response` files: the `x` coordinate and a flag selecting `y`.
+    Compressed,
+    /// `challenge` files: both coordinates.
+    Uncompressed,
+}
+
+impl PointEncoding {
+    fn coordinates(self) -> usize {
+        match self {
+            Self::Compressed => 1,
+            Self::Uncompressed => 2,
+        }
+    }
+}
+
+/// Size of a BN254 base field element in bellman's encoding.
+const FQ_BYTES: usize = 32;
+
+impl PowersOfTau<Bn254> {
+    /// Reads the powers KZG needs for polynomials up to `max_degree` from a
+    /// perpetual powers-of-tau file of a ceremony of size `2^power`, laid out
+    /// as in the [module docs](self), and verifies them. The rest of the file
+    /// is not read, so a small SRS can be taken from a large ceremony.
+    pub fn read_perpetual<R: Read, Rng: RngCore>(
+        mut reader: R,
+        power: u32,
+        encoding: PointEncoding,
+        max_degree: usize,
+        rng: &mut Rng,
+    ) -> Result<Self, TranscriptError> {
+        let num_powers = 1usize << power;
+        // The hiding powers run out before the powers of tau in G1
+        if num_powers < max_degree + 2 {
+            return Err(TranscriptError::TooFewPowers {
+                section: "alpha_tau_powers_g1",
+                required: max_degree + 2,
+                found: num_powers,
+            });
+        }
+
+        skip(&mut reader, 64)?;
+        let tau_powers_g1 = read_section(
+            &mut reader,
+            encoding,
+            2 * num_powers - 1,
+            max_degree + 1,
+            |coordinates| coordinates[0],
+        )?;
+        // Only `G2` and `τ · G2` go into the SRS
+        let tau_powers_g2 = read_section(&mut reader, encoding, num_powers, 2, |coordinates| {
+            Fq2::new(coordinates[1], coordinates[0])
+        })?;
+        let alpha_tau_powers_g1 = read_section(
+            &mut reader,
+            encoding,
+            num_powers,
+            max_degree + 2,
+            |coordinates| coordinates[0],
+        )?;
+
+        let transcript = Self {
+            tau_powers_g1,
+            tau_powers_g2,
+            alpha_tau_powers_g1,
+        };
+        transcript.verify(rng)?;
+        Ok(transcript)
+    }
+}
+
+/// Reads the first `kept` of the `count` points of a section and skips the
+/// others. `field` assembles a coordinate from its base field elements, in
+/// the order they are stored.
+fn read_section<P, R>(
+    reader: &mut R,
+    encoding: PointEncoding,
+    count: usize,
+    kept: usize,
+    field: impl Fn(&[Fq]) -> P::BaseField,
+) -> Result<Vec<GroupAffine<P>>, SerializationError>
+where
+    P: SWModelParameters,
+    R: Read,
+{
+    let degree = P::BaseField::extension_degree() as usize;
+    let point_bytes = encoding.coordinates() * degree * FQ_BYTES;
+    let points = (0..kept)
+        .map(|_| {
+            let mut bytes = vec![0; point_bytes];
+            reader.read_exact(&mut bytes)?;
+            decode_point(bytes, encoding, degree, &field)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    skip(reader, ((count - kept) * point_bytes) as u64)?;
+    Ok(points)
+}
+
+/// Decodes a point in bellman's encoding and checks that it is in the prime
+/// order subgroup.
+fn decode_point<P: SWModelParameters>(
+    mut bytes: Vec<u8>,
+    encoding: PointEncoding,
+    degree: usize,
+    field: impl Fn(&[Fq]) -> P::BaseField,
+) -> Result<GroupAffine<P>, SerializationError> {
+    let (infinity, greatest) = (bytes[0] & 0x40 != 0, bytes[0] & 0x80 != 0);
+    bytes[0] &= 0x3f;
+    // No power of a nonzero secret is the point at infinity
+    if infinity {
+        return Err(SerializationError::InvalidData);
+    }
+    let elements = bytes
+        .chunks(FQ_BYTES)
+        .map(|chunk| {
+            let mut repr = chunk.to_vec();
+            repr.reverse();
+            Fq::from_repr(FromBytes::read(&*repr)?).ok_or(SerializationError::InvalidData)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let point = match encoding {
+        PointEncoding::Compressed => GroupAffine::<P>::get_point_from_x(field(&elements), greatest)
+            .ok_or(SerializationError::InvalidData)?,
+        PointEncoding::Uncompressed => {
+            let point = GroupAffine::<P>::new(
+                field(&elements[..degree]),
+                field(&elements[degree..]),
+                false,
+            );
+            if greatest || !point.is_on_curve() {
+                return Err(SerializationError::InvalidData);
+            }
+            point
+        }
+    };
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(point)
+}
+
+/// Reads and drops `num_bytes` bytes, failing if the reader ends first.
+fn skip<R: Read>(reader: &mut R, num_bytes: u64) -> Result<(), SerializationError> {
+    let skipped = std::io::copy(&mut reader.take(num_bytes), &mut std::io::sink())?;
+    if skipped < num_bytes {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(())
+}
+
+/// Random linear combinations `(Σ r_i·p_(i+1), Σ r_i·p_i)` of consecutive
+/// points, which are in ratio `τ` iff every pair is (except with negligible
+/// probability over the `r_i`).
+fn same_ratio<G: AffineCurve, R: RngCore>(points: &[G], rng: &mut R) -> (G, G) {
+    random_combination(&points[1..], &points[..points.len() - 1], rng)
+}
+
+/// The same random linear combination `(Σ r_i·lhs_i, Σ r_i·rhs_i)` of two
+/// sequences of points, to check all pairs `(lhs_i, rhs_i)` for one ratio with
+/// a single pairing equation.
+pub(crate) fn random_combination<G: AffineCurve, R: RngCore>(
+    lhs: &[G],
+    rhs: &[G],
+    rng: &mut R,
+) -> (G, G) {
+    let scalars = (0..lhs.len())
+        .map(|_| G::ScalarField::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+    (
+        VariableBaseMSM::multi_scalar_mul(lhs, &scalars).into_affine(),
+        VariableBaseMSM::multi_scalar_mul(rhs, &scalars).into_affine(),
+    )
+}
+
+/// `[s · g for s in scalars]`, in affine form.
+pub(crate) fn fixed_base_mul<G: ProjectiveCurve>(
+    g: G,
+    scalars: &[G::ScalarField],
+) -> Vec<G::Affine> {
+    let scalar_bits = G::ScalarField::size_in_bits();
+    let window = FixedBaseMSM::get_mul_window_size(scalars.len());
+    let table = FixedBaseMSM::get_window_table(scalar_bits, window, g);
+    G::batch_normalization_into_affine(&FixedBaseMSM::multi_scalar_mul(
+        scalar_bits,
+        window,
+        &table,
+        scalars,
+    ))
+}
+
+/// `[1, x, x^2, ..., x^(n-1)]`.
+pub(crate) fn powers<F: PrimeField>(x: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = F::one();
+    for _ in 0..n {
+        powers.push(power);
+        power *= x;
+    }
+    powers
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, G1Affine};
+    use ark_bn254::Fr;
+    use ark_ff::BigInteger;
+    use core::str::FromStr;
+
+    /// Transcript of `n` powers in G1 for a random `τ` and `α`.
+    pub(crate) fn sample<E: PairingEngine, R: RngCore>(n: usize, rng: &mut R) -> PowersOfTau<E> {
+        PowersOfTau::from_secrets(E::Fr::rand(rng), E::Fr::rand(rng), n, 2)
+    }
+
+    fn encode(transcript: &PowersOfTau<Bls12_381>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        transcript.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn accepts_honest_transcript() {
+        let mut rng = ark_std::test_rng();
+        let transcript = sample::<Bls12_381, _>(32, &mut rng);
+
+        let read = PowersOfTau::<Bls12_381>::read(&*encode(&transcript), &mut rng).unwrap();
+        assert_eq!(read, transcript);
+        assert_eq!(read.max_degree(), 31);
+
+        let params = read.universal_params(15).unwrap();
+        assert_eq!(params.powers_of_g.len(), 16);
+        assert_eq!(params.powers_of_gamma_g.len(), 17);
+
+        // The full degree keeps every hiding power, and no degree is truncated
+        let params = read.universal_params(31).unwrap();
+        assert_eq!(params.powers_of_gamma_g.len(), 33);
+        assert!(matches!(
+            read.universal_params(32),
+            Err(TranscriptError::TooFewPowers {
+                section: "tau_powers_g1",
+                ..
+            })
+        ));
+
+        // Fewer hiding powers, as in perpetual powers-of-tau files, only lower
+        // the degree
+        let mut short = transcript.clone();
+        short.alpha_tau_powers_g1.truncate(16);
+        let read = PowersOfTau::<Bls12_381>::read(&*encode(&short), &mut rng).unwrap();
+        assert_eq!(read.max_degree(), 14);
+        assert!(read.universal_params(14).is_ok());
+        assert!(matches!(
+            read.universal_params(15),
+            Err(TranscriptError::TooFewPowers {
+                section: "alpha_tau_powers_g1",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_transcripts() {
+        let mut rng = ark_std::test_rng();
+        let transcript = sample::<Bls12_381, _>(32, &mut rng);
+
+        // One power replaced by an unrelated point
+        let mut tampered = transcript.clone();
+        tampered.tau_powers_g1[7] = G1Affine::prime_subgroup_generator()
+            .mul(<Bls12_381 as PairingEngine>::Fr::rand(&mut rng))
+            .into_affine();
+        assert!(matches!(
+            PowersOfTau::<Bls12_381>::read(&*encode(&tampered), &mut rng),
+            Err(TranscriptError::InconsistentPowers("tau_powers_g1"))
+        ));
+
+        // Hiding powers for a different tau
+        let mut tampered = transcript.clone();
+        tampered.alpha_tau_powers_g1 = sample::<Bls12_381, _>(32, &mut rng).alpha_tau_powers_g1;
+        assert!(matches!(
+            PowersOfTau::<Bls12_381>::read(&*encode(&tampered), &mut rng),
+            Err(TranscriptError::InconsistentPowers("alpha_tau_powers_g1"))
+        ));
+
+        let mut tampered = transcript.clone();
+        tampered.tau_powers_g2.truncate(1);
+        assert!(matches!(
+            PowersOfTau::<Bls12_381>::read(&*encode(&tampered), &mut rng),
+            Err(TranscriptError::TooFewPowers {
+                section: "tau_powers_g2",
+                ..
+            })
+        ));
+
+        // Truncated encodings do not parse
+        let bytes = encode(&transcript);
+        assert!(matches!(
+            PowersOfTau::<Bls12_381>::read(&bytes[..bytes.len() - 1], &mut rng),
+            Err(TranscriptError::Serialization(_))
+        ));
+    }
+
+    /// Bellman's encoding of a BN254 point, as in perpetual powers-of-tau files.
+    fn encode_bellman<P: SWModelParameters>(
+        point: &GroupAffine<P>,
+        encoding: PointEncoding,
+        limbs: impl Fn(&P::BaseField) -> Vec<Fq>,
+    ) -> Vec<u8> {
+        let mut coordinates = limbs(&point.x);
+        if encoding == PointEncoding::Uncompressed {
+            coordinates.extend(limbs(&point.y));
+        }
+        let mut bytes = coordinates
+            .iter()
+            .flat_map(|limb| limb.into_repr().to_bytes_be())
+            .collect::<Vec<_>>();
+        if encoding == PointEncoding::Compressed && point.y > -point.y {
+            bytes[0] |= 0x80;
+        }
+        bytes
+    }
+
+    fn encode_g1(point: &ark_bn254::G1Affine, encoding: PointEncoding) -> Vec<u8> {
+        encode_bellman(point, encoding, |x| vec![*x])
+    }
+
+    fn encode_g2(point: &ark_bn254::G2Affine, encoding: PointEncoding) -> Vec<u8> {
+        encode_bellman(point, encoding, |x| vec![x.c1, x.c0])
+    }
+
+    /// A perpetual powers-of-tau file of size `2^power` for a random `τ` and
+    /// `α`, up to its hiding powers, and the transcript it holds.
+    fn perpetual_file(
+        power: u32,
+        encoding: PointEncoding,
+        rng: &mut impl RngCore,
+    ) -> (Vec<u8>, PowersOfTau<Bn254>) {
+        let num_powers = 1 << power;
+        let mut transcript = PowersOfTau::<Bn254>::from_secrets(
+            Fr::rand(rng),
+            Fr::rand(rng),
+            2 * num_powers - 1,
+            num_powers,
+        );
+        transcript.alpha_tau_powers_g1.truncate(num_powers);
+
+        let mut bytes = vec![0; 64];
+        for point in &transcript.tau_powers_g1 {
+            bytes.extend(encode_g1(point, encoding));
+        }
+        for point in &transcript.tau_powers_g2 {
+            bytes.extend(encode_g2(point, encoding));
+        }
+        for point in &transcript.alpha_tau_powers_g1 {
+            bytes.extend(encode_g1(point, encoding));
+        }
+        (bytes, transcript)
+    }
+
+    #[test]
+    fn decodes_bellman_generators() {
+        // The first powers of every perpetual powers-of-tau file: G1 = (1, 2),
+        // and G2 with the coordinates of EIP-197
+        let g1 = [[0; 31].as_slice(), &[1], &[0; 31], &[2]].concat();
+        let g2 = [
+            "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+            "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+            "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+            "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+        ]
+        .iter()
+        .flat_map(|x| Fq::from_str(x).unwrap().into_repr().to_bytes_be())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            decode_point(g1, PointEncoding::Uncompressed, 1, |x| x[0]).unwrap(),
+            ark_bn254::G1Affine::prime_subgroup_generator()
+        );
+        assert_eq!(
+            decode_point(g2, PointEncoding::Uncompressed, 2, |x| Fq2::new(x[1], x[0])).unwrap(),
+            ark_bn254::G2Affine::prime_subgroup_generator()
+        );
+        // 2 is the smaller of the two roots, so the compressed flag is unset
+        assert_eq!(
+            decode_point(
+                [[0; 31].as_slice(), &[1]].concat(),
+                PointEncoding::Compressed,
+                1,
+                |x| x[0]
+            )
+            .unwrap(),
+            ark_bn254::G1Affine::prime_subgroup_generator()
+        );
+    }
+
+    #[test]
+    fn reads_perpetual_files() {
+        let mut rng = ark_std::test_rng();
+        for encoding in [PointEncoding::Compressed, PointEncoding::Uncompressed] {
+            let (bytes, transcript) = perpetual_file(3, encoding, &mut rng);
+
+            let read = PowersOfTau::read_perpetual(&*bytes, 3, encoding, 6, &mut rng).unwrap();
+            assert_eq!(read.tau_powers_g1, transcript.tau_powers_g1[..7]);
+            assert_eq!(read.tau_powers_g2, transcript.tau_powers_g2[..2]);
+            assert_eq!(read.alpha_tau_powers_g1, transcript.alpha_tau_powers_g1);
+            assert_eq!(read.max_degree(), 6);
+
+            // 2^3 hiding powers back degree 6 at most
+            assert!(matches!(
+                PowersOfTau::read_perpetual(&*bytes, 3, encoding, 7, &mut rng),
+                Err(TranscriptError::TooFewPowers {
+                    section: "alpha_tau_powers_g1",
+                    ..
+                })
+            ));
+            assert!(matches!(
+                PowersOfTau::read_perpetual(&bytes[..bytes.len() - 1], 3, encoding, 6, &mut rng),
+                Err(TranscriptError::Serialization(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_perpetual_files() {
+        let mut rng = ark_std::test_rng();
+        let (bytes, _) = perpetual_file(3, PointEncoding::Compressed, &mut rng);
+        let offset = |index: usize| 64 + index * 32;
+
+        // Flipping the root flag negates one power
+        let mut tampered = bytes.clone();
+        tampered[offset(3)] ^= 0x80;
+        assert!(matches!(
+            PowersOfTau::read_perpetual(&*tampered, 3, PointEncoding::Compressed, 6, &mut rng),
+            Err(TranscriptError::InconsistentPowers("tau_powers_g1"))
+        ));
+
+        let mut tampered = bytes;
+        tampered[offset(3)] |= 0x40;
+        assert!(matches!(
+            PowersOfTau::read_perpetual(&*tampered, 3, PointEncoding::Compressed, 6, &mut rng),
+            Err(TranscriptError::Serialization(_))
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+//! Backend-agnostic interface to the proof systems membership circuits are
+//! proven with.
+//!
+//! Code written against [`MembershipProver`] and [`MembershipVerifier`] can
+//! switch between [`Groth16`], [`GM17`] and [`MarlinKzg`](crate::marlin::MarlinKzg)
+//! by changing a single type parameter.
+
+use ark_crypto_primitives::SNARK;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+use ark_gm17::GM17;
+use ark_groth16::Groth16;
+use ark_marlin::{AHPForR1CS, Marlin};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::PolynomialCommitment;
+use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::rand::{CryptoRng, RngCore};
+use blake2::Digest;
+use core::fmt::Debug;
+
+/// Proof system that checks membership proofs over the circuit field `F`.
+pub trait MembershipVerifier<F: PrimeField> {
+    type VerifyingKey: CanonicalSerialize + CanonicalDeserialize;
+    type Proof: CanonicalSerialize + CanonicalDeserialize;
+    type Error: Debug;
+
+    /// Checks `proof` against the circuit's public inputs, in allocation order.
+    /// `rng` is only drawn from by proof systems with randomized verifiers.
+    fn verify<R: RngCore>(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[F],
+        proof: &Self::Proof,
+        rng: &mut R,
+    ) -> Result<bool, Self::Error>;
+
+    fn verifying_key_to_bytes(vk: &Self::VerifyingKey) -> Result<Vec<u8>, SerializationError> {
+        to_bytes(vk)
+    }
+
+    fn verifying_key_from_bytes(bytes: &[u8]) -> Result<Self::VerifyingKey, SerializationError> {
+        Self::VerifyingKey::deserialize(bytes)
+    }
+
+    fn proof_to_bytes(proof: &Self::Proof) -> Result<Vec<u8>, SerializationError> {
+        to_bytes(proof)
+    }
+
+    fn proof_from_bytes(bytes: &[u8]) -> Result<Self::Proof, SerializationError> {
+        Self::Proof::deserialize(bytes)
+    }
+}
+
+/// Proof system that creates membership proofs over the circuit field `F`.
+pub trait MembershipProver<F: PrimeField>: MembershipVerifier<F> {
+    type ProvingKey: CanonicalSerialize + CanonicalDeserialize;
+
+    /// Generates keys for the shape of `circuit`. Its witness is not used, so a
+    /// circuit for any member of a tree of the same depth will do.
+    fn setup<C: ConstraintSynthesizer<F> + Clone, R: RngCore + CryptoRng>(
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error>;
+
+    fn prove<C: ConstraintSynthesizer<F>, R: RngCore + CryptoRng>(
+        pk: &Self::ProvingKey,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error>;
+
+    fn proving_key_to_bytes(pk: &Self::ProvingKey) -> Result<Vec<u8>, SerializationError> {
+        to_bytes(pk)
+    }
+
+    fn proving_key_from_bytes(bytes: &[u8]) -> Result<Self::ProvingKey, SerializationError> {
+        Self::ProvingKey::deserialize(bytes)
+    }
+}
+
+fn to_bytes<T: CanonicalSerialize>(value: &T) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = Vec::with_capacity(value.serialized_size());
+    value.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/////////////////////////////
+
+impl<E: PairingEngine> MembershipVerifier<E::Fr> for Groth16<E> {
+    type VerifyingKey = ark_groth16::VerifyingKey<E>;
+    type Proof = ark_groth16::Proof<E>;
+    type Error = SynthesisError;
+
+    fn verify<R: RngCore>(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[E::Fr],
+        proof: &Self::Proof,
+        _rng: &mut R,
+    ) -> Result<bool, Self::Error> {
+        <Self as SNARK<E::Fr>>::verify(vk, public_inputs, proof)
+    }
+}
+
+impl<E: PairingEngine> MembershipProver<E::Fr> for Groth16<E> {
+    type ProvingKey = ark_groth16::ProvingKey<E>;
+
+    fn setup<C: ConstraintSynthesizer<E::Fr> + Clone, R: RngCore + CryptoRng>(
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error> {
+        Self::circuit_specific_setup(circuit, rng)
+    }
+
+    fn prove<C: ConstraintSynthesizer<E::Fr>, R: RngCore + CryptoRng>(
+        pk: &Self::ProvingKey,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        <Self as SNARK<E::Fr>>::prove(pk, circuit, rng)
+    }
+}
+
+/////////////////////////////
+
+impl<E: PairingEngine> MembershipVerifier<E::Fr> for GM17<E> {
+    type VerifyingKey = ark_gm17::VerifyingKey<E>;
+    type Proof = ark_gm17::Proof<E>;
+    type Error = SynthesisError;
+
+    fn verify<R: RngCore>(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[E::Fr],
+        proof: &Self::Proof,
+        _rng: &mut R,
+    ) -> Result<bool, Self::Error> {
+        <Self as SNARK<E::Fr>>::verify(vk, public_inputs, proof)
+    }
+}
+
+impl<E: PairingEngine> MembershipProver<E::Fr> for GM17<E> {
+    type ProvingKey = ark_gm17::ProvingKey<E>;
+
+    fn setup<C: ConstraintSynthesizer<E::Fr> + Clone, R: RngCore + CryptoRng>(
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error> {
+        Self::circuit_specific_setup(circuit, rng)
+    }
+
+    fn prove<C: ConstraintSynthesizer<E::Fr>, R: RngCore + CryptoRng>(
+        pk: &Self::ProvingKey,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        <Self as SNARK<E::Fr>>::prove(pk, circuit, rng)
+    }
+}
+
+/////////////////////////////
+
+impl<F, PC, D> MembershipVerifier<F> for Marlin<F, PC, D>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DensePolynomial<F>>,
+    D: Digest,
+{
+    type VerifyingKey = ark_marlin::IndexVerifierKey<F, PC>;
+    type Proof = ark_marlin::Proof<F, PC>;
+    type Error = ark_marlin::Error<PC::Error>;
+
+    fn verify<R: RngCore>(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[F],
+        proof: &Self::Proof,
+        rng: &mut R,
+    ) -> Result<bool, Self::Error> {
+        Self::verify(vk, public_inputs, proof, rng)
+    }
+}
+
+impl<F, PC, D> MembershipProver<F> for Marlin<F, PC, D>
+where
+    F: PrimeField,
+    PC: PolynomialCommitment<F, DensePolynomial<F>>,
+    D: Digest,
+{
+    type ProvingKey = ark_marlin::IndexProverKey<F, PC>;
+
+    /// Runs a universal setup sized exactly for `circuit`, then indexes it. The
+    /// universal parameters are discarded.
+    fn setup<C: ConstraintSynthesizer<F> + Clone, R: RngCore + CryptoRng>(
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error> {
+        let info = AHPForR1CS::<F>::index(circuit.clone())?.index_info;
+        let srs = Self::universal_setup(
+            info.num_constraints,
+            info.num_variables,
+            info.num_non_zero,
+            rng,
+        )?;
+        Self::index(&srs, circuit)
+    }
+
+    fn prove<C: ConstraintSynthesizer<F>, R: RngCore + CryptoRng>(
+        pk: &Self::ProvingKey,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        Self::prove(pk, circuit, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::pedersen381::{
+        common::{new_membership_tree, LeafHash, TwoToOneHash},
+        constraint::MerkleTreeCircuit,
+    };
+    use crate::marlin::MarlinKzg;
+    use crate::member::Member;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_crypto_primitives::{crh::TwoToOneCRH, CRH};
+
+    /// Runs a membership proof through `S` only via the traits.
+    fn prove_and_verify<S: MembershipProver<Fr>>() {
+        let mut rng = ark_std::test_rng();
+
+        let leaf_crh_params = <LeafHash as CRH>::setup(&mut rng).unwrap();
+        let two_to_one_crh_params = <TwoToOneHash as TwoToOneCRH>::setup(&mut rng).unwrap();
+
+        let members = [
+            Member::new("alice".into(), "alice@usc.edu".into(), None),
+            Member::new("bob".into(), "bob@usc.edu".into(), None),
+        ];
+        let mut leaves = members
+            .iter()
+            .map(|member| member.hash::<LeafHash>(&leaf_crh_params).unwrap())
+            .collect::<Vec<_>>();
+        let tree = new_membership_tree(&leaf_crh_params, &two_to_one_crh_params, &mut leaves);
+        let root = tree.root();
+
+        let circuit = MerkleTreeCircuit {
+            leaf_crh_params,
+            two_to_one_crh_params,
+            root,
+            leaf_hash: leaves[1],
+            authentication_path: Some(tree.generate_proof(1).unwrap()),
+        };
+
+        let (pk, vk) = S::setup(circuit.clone(), &mut rng).unwrap();
+        let pk = S::proving_key_from_bytes(&S::proving_key_to_bytes(&pk).unwrap()).unwrap();
+        let proof = S::prove(&pk, circuit, &mut rng).unwrap();
+
+        // Only serialized keys and proofs reach the verifier
+        let vk = S::verifying_key_from_bytes(&S::verifying_key_to_bytes(&vk).unwrap()).unwrap();
+        let proof = S::proof_from_bytes(&S::proof_to_bytes(&proof).unwrap()).unwrap();
+
+        assert!(S::verify(&vk, &[root, leaves[1]], &proof, &mut rng).unwrap());
+        assert!(!S::verify(&vk, &[root, leaves[0]], &proof, &mut rng).unwrap());
+    }
+
+    #[test]
+    fn groth16() {
+        prove_and_verify::<Groth16<Bls12_381>>();
+    }
+
+    #[test]
+    fn gm17() {
+        prove_and_verify::<GM17<Bls12_381>>();
+    }
+
+    #[test]
+    fn marlin_kzg() {
+        prove_and_verify::<MarlinKzg<Bls12_381>>();
+    }
+}